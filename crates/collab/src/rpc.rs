@@ -395,6 +395,14 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::RestartLanguageServers>)
             .add_request_handler(forward_mutating_project_request::<proto::StopLanguageServers>)
             .add_request_handler(forward_mutating_project_request::<proto::LinkedEditingRange>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetHierarchyLocations>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetInlineValues>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetFoldingRanges>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSelectionRanges>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetDocumentLinks>)
+            .add_request_handler(forward_read_only_project_request::<proto::ResolveDocumentLink>)
+            .add_request_handler(forward_read_only_project_request::<proto::ResolveCodeLens>)
             .add_message_handler(create_buffer_for_peer)
             .add_message_handler(create_image_for_peer)
             .add_request_handler(update_buffer)
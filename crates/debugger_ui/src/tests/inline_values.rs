@@ -2,9 +2,10 @@ use std::{path::Path, sync::Arc};
 
 use dap::{Scope, StackFrame, Variable, requests::Variables};
 use editor::{Editor, EditorMode, MultiBuffer};
+use futures::StreamExt as _;
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use language::{
-    Language, LanguageConfig, LanguageMatcher, rust_lang, tree_sitter_python,
+    FakeLspAdapter, Language, LanguageConfig, LanguageMatcher, rust_lang, tree_sitter_python,
     tree_sitter_typescript,
 };
 use project::{FakeFs, Project};
@@ -2185,6 +2186,222 @@ fn main() {
     .await;
 }
 
+#[gpui::test]
+async fn test_lsp_inline_values(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let source_code = r#"
+fn main() {
+    let x = 10;
+    let y = x * 2;
+    println!("{}", y);
+}
+"#
+    .unindent();
+    fs.insert_tree(path!("/project"), json!({ "main.rs": source_code }))
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                inline_value_provider: Some(lsp::OneOf::Left(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(|_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "main".into(),
+            }],
+        })
+    });
+    client.on_request::<dap::requests::StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 4,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Local".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: [("x", "10"), ("y", "20")]
+                .into_iter()
+                .map(|(name, value)| Variable {
+                    name: name.into(),
+                    value: value.into(),
+                    type_: None,
+                    presentation_hint: None,
+                    evaluate_name: None,
+                    variables_reference: 0,
+                    named_variables: None,
+                    indexed_variables: None,
+                    memory_reference: None,
+                    declaration_location_reference: None,
+                    value_location_reference: None,
+                })
+                .collect(),
+        })
+    });
+    client.on_request::<dap::requests::Evaluate, _>(move |_, args| {
+        assert_eq!("x * 2", args.expression);
+        Ok(dap::EvaluateResponse {
+            result: "20".into(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let (buffer, _buffer_handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/project/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::InlineValueRequest, _, _>(|_, _| async move {
+        Ok(Some(vec![
+            lsp::InlineValue::Text(lsp::InlineValueText {
+                range: lsp::Range::new(lsp::Position::new(1, 15), lsp::Position::new(1, 15)),
+                text: "x = 10".to_owned(),
+            }),
+            lsp::InlineValue::VariableLookup(lsp::InlineValueVariableLookup {
+                range: lsp::Range::new(lsp::Position::new(2, 8), lsp::Position::new(2, 9)),
+                variable_name: None,
+                case_sensitive_lookup: true,
+            }),
+            lsp::InlineValue::EvaluatableExpression(lsp::InlineValueEvaluatableExpression {
+                range: lsp::Range::new(lsp::Position::new(2, 12), lsp::Position::new(2, 17)),
+                expression: None,
+            }),
+        ]))
+    });
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project),
+            window,
+            cx,
+        )
+    });
+    active_debug_session_panel(workspace, cx).update_in(cx, |_, window, cx| {
+        cx.focus_self(window);
+    });
+    cx.run_until_parked();
+
+    editor.update(cx, |editor, cx| editor.refresh_inline_values(cx));
+    cx.run_until_parked();
+    editor.update_in(cx, |editor, window, cx| {
+        pretty_assertions::assert_eq!(
+            r#"
+    fn main() {
+        let x = 10; x = 10
+        let y: 20 = x * 2: 20;
+        println!("{}", y);
+    }
+    "#
+            .unindent(),
+            editor.snapshot(window, cx).text(),
+            "The language server's values should be shown instead of the tree-sitter ones"
+        );
+    });
+
+    fake_server.set_request_handler::<lsp::request::InlineValueRequest, _, _>(|_, _| async move {
+        Ok(None)
+    });
+    editor.update(cx, |editor, cx| editor.refresh_inline_values(cx));
+    cx.run_until_parked();
+    editor.update_in(cx, |editor, window, cx| {
+        pretty_assertions::assert_eq!(
+            r#"
+    fn main() {
+        let x: 10 = 10;
+        let y: 20 = x: 10 * 2;
+        println!("{}", y: 20);
+    }
+    "#
+            .unindent(),
+            editor.snapshot(window, cx).text(),
+            "Without values from the language server, the tree-sitter queries should be used"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_go_inline_values(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     let variables = [("x", "42"), ("y", "hello")];
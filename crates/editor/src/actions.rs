@@ -471,10 +471,18 @@ actions!(
         CollapseAllDiffHunks,
         /// Expands macros recursively at cursor position.
         ExpandMacroRecursively,
+        /// Opens a multibuffer with the call sites of the symbol under the cursor.
+        FindIncomingCalls,
         /// Finds the next match in the search.
         FindNextMatch,
+        /// Opens a multibuffer with the functions called by the symbol under the cursor.
+        FindOutgoingCalls,
         /// Finds the previous match in the search.
         FindPreviousMatch,
+        /// Opens a multibuffer with the subtypes of the type under the cursor.
+        FindSubtypes,
+        /// Opens a multibuffer with the supertypes of the type under the cursor.
+        FindSupertypes,
         /// Folds the current code block.
         Fold,
         /// Folds all foldable regions in the editor.
@@ -751,6 +759,8 @@ actions!(
         ShowCompletions,
         /// Shows the system character palette.
         ShowCharacterPalette,
+        /// Shows the language server's alternative presentations for the color under the cursor.
+        ShowColorPresentations,
         /// Shows edit prediction at cursor.
         ShowEditPrediction,
         /// Shows signature help for the current function.
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use collections::{HashMap, HashSet};
+use futures::future::join_all;
+use gpui::{Task, WeakEntity};
+use itertools::Itertools as _;
+use language::Point;
+use multi_buffer::Anchor;
+use project::{CodeAction, LspAction};
+use settings::Settings as _;
+use text::{BufferId, ToOffset as _, ToPoint as _};
+use ui::prelude::*;
+use util::ResultExt as _;
+
+use crate::{
+    Editor, EditorSettings, FETCH_CODE_LENS_DEBOUNCE_TIMEOUT,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+};
+
+pub(super) struct CodeLensState {
+    enabled: bool,
+    blocks: HashMap<BufferId, Vec<CustomBlockId>>,
+    refresh_task: Task<()>,
+}
+
+impl CodeLensState {
+    pub(super) fn new(cx: &App) -> Self {
+        Self {
+            enabled: EditorSettings::get_global(cx).lsp_code_lens,
+            blocks: HashMap::default(),
+            refresh_task: Task::ready(()),
+        }
+    }
+
+    /// Returns whether the setting changed, so that the caller knows lenses need to be refetched or removed.
+    pub(super) fn enabled_updated(&mut self, enabled: bool) -> bool {
+        if self.enabled == enabled {
+            false
+        } else {
+            self.enabled = enabled;
+            true
+        }
+    }
+}
+
+impl Editor {
+    pub(super) fn refresh_code_lens(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return;
+        }
+        if !self.code_lens.enabled {
+            self.code_lens.refresh_task = Task::ready(());
+            let blocks = self
+                .code_lens
+                .blocks
+                .drain()
+                .flat_map(|(_, blocks)| blocks)
+                .collect::<HashSet<_>>();
+            if !blocks.is_empty() {
+                self.remove_blocks(blocks, None, cx);
+            }
+            return;
+        }
+
+        self.code_lens.refresh_task = cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_CODE_LENS_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Ok(code_lens_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                editor
+                    .visible_excerpts(true, cx)
+                    .into_values()
+                    .map(|(buffer, ..)| buffer)
+                    .filter(|editor_buffer| {
+                        let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                        for_buffer.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                            && editor.registered_buffers.contains_key(&editor_buffer_id)
+                    })
+                    .unique_by(|buffer| buffer.read(cx).remote_id())
+                    .map(|buffer| {
+                        let code_lens_task = project
+                            .read(cx)
+                            .lsp_store()
+                            .update(cx, |lsp_store, cx| lsp_store.code_lens_actions(&buffer, cx));
+                        async move { (buffer, code_lens_task.await) }
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let all_code_lens = join_all(code_lens_tasks).await;
+            let Ok(resolve_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                all_code_lens
+                    .into_iter()
+                    .filter_map(|(buffer, code_lens)| match code_lens {
+                        Ok(code_lens) => {
+                            let buffer_id = buffer.read(cx).remote_id();
+                            let resolve_tasks = code_lens
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|action| {
+                                    project.update(cx, |project, cx| {
+                                        project.resolve_code_lens(&buffer, action, cx)
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+                            Some(async move { (buffer_id, join_all(resolve_tasks).await) })
+                        }
+                        Err(e) => {
+                            log::error!("Failed to fetch code lens: {e:#}");
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let all_code_lens = join_all(resolve_tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (buffer_id, code_lens) in all_code_lens {
+                        let code_lens = code_lens
+                            .into_iter()
+                            .filter_map(|action| action.context("resolving code lens").log_err())
+                            .collect();
+                        editor.set_code_lens_blocks(buffer_id, code_lens, cx);
+                    }
+                })
+                .ok();
+        });
+    }
+
+    fn set_code_lens_blocks(
+        &mut self,
+        buffer_id: BufferId,
+        code_lens: Vec<CodeAction>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(old_blocks) = self.code_lens.blocks.remove(&buffer_id) {
+            self.remove_blocks(old_blocks.into_iter().collect(), None, cx);
+        }
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let buffer_snapshot = buffer.read(cx).snapshot();
+
+        let lens_by_row = code_lens
+            .into_iter()
+            .filter(|action| match &action.lsp_action {
+                // Lenses that the server failed to resolve have no command, hence no title to show.
+                LspAction::CodeLens(lens) => lens.command.is_some(),
+                _ => false,
+            })
+            .sorted_by_key(|action| action.range.start.to_offset(&buffer_snapshot))
+            .chunk_by(|action| action.range.start.to_point(&buffer_snapshot).row)
+            .into_iter()
+            .map(|(row, actions)| (row, actions.collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        if lens_by_row.is_empty() {
+            return;
+        }
+
+        let excerpts = self.buffer.read(cx).excerpts_for_buffer(buffer_id, cx);
+        let editor = cx.weak_entity();
+        let mut blocks = Vec::new();
+        for (row, actions) in lens_by_row {
+            let indent = buffer_snapshot.indent_size_for_line(row);
+            let position = buffer_snapshot.anchor_after(Point::new(row, indent.len));
+            for (excerpt_id, excerpt_range) in &excerpts {
+                if excerpt_range
+                    .context
+                    .start
+                    .cmp(&position, &buffer_snapshot)
+                    .is_gt()
+                    || excerpt_range
+                        .context
+                        .end
+                        .cmp(&position, &buffer_snapshot)
+                        .is_lt()
+                {
+                    continue;
+                }
+                let editor = editor.clone();
+                let actions = actions.clone();
+                blocks.push(BlockProperties {
+                    style: BlockStyle::Flex,
+                    placement: BlockPlacement::Above(Anchor::in_buffer(*excerpt_id, position)),
+                    height: Some(1),
+                    render: Arc::new(move |cx: &mut BlockContext| {
+                        render_code_lens_line(buffer_id, &actions, &editor, cx)
+                    }),
+                    priority: 0,
+                });
+            }
+        }
+
+        let block_ids = self.insert_blocks(blocks, None, cx);
+        self.code_lens.blocks.insert(buffer_id, block_ids);
+    }
+
+    fn execute_code_lens(
+        &mut self,
+        buffer_id: BufferId,
+        action: CodeAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let title = action.lsp_action.title().to_owned();
+        let apply_code_lens = project.update(cx, |project, cx| {
+            project.apply_code_action(buffer, action, true, cx)
+        });
+        let workspace = workspace.downgrade();
+        cx.spawn_in(window, async move |editor, cx| {
+            let project_transaction = apply_code_lens.await?;
+            Self::open_project_transaction(&editor, workspace, project_transaction, title, cx).await
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+fn render_code_lens_line(
+    buffer_id: BufferId,
+    actions: &[CodeAction],
+    editor: &WeakEntity<Editor>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    h_flex()
+        .id(cx.block_id)
+        .block_mouse_except_scroll()
+        .h(cx.line_height)
+        .pl(cx.anchor_x)
+        .gap_2()
+        .children(actions.iter().enumerate().map(|(ix, action)| {
+            let editor = editor.clone();
+            let action = action.clone();
+            Button::new(("code-lens", ix), action.lsp_action.title().to_owned())
+                .label_size(LabelSize::Small)
+                .color(Color::Muted)
+                .on_click(move |_, window, cx| {
+                    editor
+                        .update(cx, |editor, cx| {
+                            editor.execute_code_lens(buffer_id, action.clone(), window, cx)
+                        })
+                        .ok();
+                })
+        }))
+        .into_any_element()
+}
@@ -10,6 +10,7 @@ use crate::{
     inlays::{Inlay, InlayContent},
 };
 use collections::BTreeSet;
+use gpui::HighlightStyle;
 use language::{Chunk, Edit, Point, TextSummary};
 use multi_buffer::{
     MBTextSummary, MultiBufferOffset, MultiBufferRow, MultiBufferRows, MultiBufferSnapshot,
@@ -366,22 +367,39 @@ impl<'a> Iterator for InlayChunks<'a> {
                         self.highlight_styles.inlay_hint
                     }
                 };
-                let next_inlay_highlight_endpoint;
+                let mut next_inlay_highlight_endpoint = usize::MAX;
                 let offset_in_inlay = self.output_offset - self.transforms.start().0;
-                if let Some((style, highlight)) = inlay_style_and_highlight {
-                    let range = &highlight.range;
+                if let InlayContent::HighlightedText(_, text_highlights) = &inlay.content
+                    && let Some((range, text_style)) = text_highlights
+                        .iter()
+                        .find(|(range, _)| offset_in_inlay < range.end)
+                {
                     if offset_in_inlay < range.start {
                         next_inlay_highlight_endpoint = range.start - offset_in_inlay;
-                    } else if offset_in_inlay >= range.end {
-                        next_inlay_highlight_endpoint = usize::MAX;
                     } else {
                         next_inlay_highlight_endpoint = range.end - offset_in_inlay;
+                        // Keep the inlay's own style, so the text still reads as an inlay,
+                        // and only take the color from the text's highlight.
+                        highlight_style = highlight_style
+                            .map(|style| HighlightStyle {
+                                color: text_style.color.or(style.color),
+                                ..style
+                            })
+                            .or(Some(*text_style));
+                    }
+                }
+                if let Some((style, highlight)) = inlay_style_and_highlight {
+                    let range = &highlight.range;
+                    if offset_in_inlay < range.start {
+                        next_inlay_highlight_endpoint =
+                            cmp::min(next_inlay_highlight_endpoint, range.start - offset_in_inlay);
+                    } else if offset_in_inlay < range.end {
+                        next_inlay_highlight_endpoint =
+                            cmp::min(next_inlay_highlight_endpoint, range.end - offset_in_inlay);
                         highlight_style = highlight_style
                             .map(|highlight| highlight.highlight(*style))
                             .or_else(|| Some(*style));
                     }
-                } else {
-                    next_inlay_highlight_endpoint = usize::MAX;
                 }
 
                 let inlay_chunks = self.inlay_chunks.get_or_insert_with(|| {
@@ -1251,7 +1269,7 @@ mod tests {
         display_map::{HighlightKey, InlayHighlights, TextHighlights},
         hover_links::InlayHighlight,
     };
-    use gpui::{App, HighlightStyle};
+    use gpui::App;
     use multi_buffer::Anchor;
     use project::{InlayHint, InlayHintLabel, ResolveState};
     use rand::prelude::*;
@@ -2226,6 +2244,72 @@ mod tests {
         assert_eq!(highlighted_chunks[0].chunk.text, "SortingDirec…");
     }
 
+    #[gpui::test]
+    fn test_highlighted_text_inlay_chunks(cx: &mut App) {
+        init_test(cx);
+
+        let buffer = MultiBuffer::build_simple("fn main() {}\n", cx);
+        let (mut inlay_map, _) = InlayMap::new(buffer.read(cx).snapshot(cx));
+        let position = buffer
+            .read(cx)
+            .snapshot(cx)
+            .anchor_before(Point::new(0, 11));
+        let keyword_style = HighlightStyle {
+            color: Some(gpui::red()),
+            ..Default::default()
+        };
+        let inlay = Inlay {
+            id: InlayId::EditPrediction(0),
+            position,
+            content: InlayContent::HighlightedText(
+                Rope::from("let x"),
+                Arc::from([(0..3, keyword_style)]),
+            ),
+        };
+        let (inlay_snapshot, _) = inlay_map.splice(&[], vec![inlay]);
+
+        let insertion_style = HighlightStyle {
+            color: Some(gpui::blue()),
+            font_weight: Some(gpui::FontWeight::BOLD),
+            ..Default::default()
+        };
+        let highlights = crate::display_map::Highlights {
+            text_highlights: None,
+            inlay_highlights: None,
+            styles: crate::display_map::HighlightStyles {
+                inlay_hint: None,
+                edit_prediction: Some(crate::display_map::EditPredictionStyles {
+                    insertion: insertion_style,
+                    whitespace: HighlightStyle::default(),
+                }),
+            },
+        };
+        let inlay_chunks = inlay_snapshot
+            .chunks(
+                InlayOffset(MultiBufferOffset(0))..inlay_snapshot.len(),
+                false,
+                highlights,
+            )
+            .filter(|chunk| chunk.chunk.is_inlay)
+            .map(|chunk| (chunk.chunk.text, chunk.chunk.highlight_style))
+            .collect::<Vec<_>>();
+
+        // The text's own colors replace the inlay color, the rest of the inlay style is kept.
+        assert_eq!(
+            inlay_chunks,
+            vec![
+                (
+                    "let",
+                    Some(HighlightStyle {
+                        color: Some(gpui::red()),
+                        ..insertion_style
+                    })
+                ),
+                (" x", Some(insertion_style)),
+            ]
+        );
+    }
+
     #[gpui::test]
     fn test_inlay_utf8_boundaries(cx: &mut App) {
         init_test(cx);
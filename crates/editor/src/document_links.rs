@@ -0,0 +1,129 @@
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{Entity, Task};
+use itertools::Itertools as _;
+use language::Buffer;
+use multi_buffer::Anchor;
+use project::{DocumentLink, ResolvedPath};
+use text::BufferId;
+use ui::{App, Context, Window};
+
+use crate::{Editor, FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT, hover_links::HoverLink};
+
+pub(super) struct DocumentLinks {
+    links: HashMap<BufferId, (clock::Global, Vec<DocumentLink>)>,
+    refresh_task: Task<()>,
+}
+
+impl DocumentLinks {
+    pub(super) fn new() -> Self {
+        Self {
+            links: HashMap::default(),
+            refresh_task: Task::ready(()),
+        }
+    }
+}
+
+impl Editor {
+    /// Fetches the links of the visible buffers that changed since they were last fetched.
+    /// A specific buffer is always refetched, as its language servers might have changed.
+    pub(super) fn refresh_document_links(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return;
+        }
+
+        self.document_links.refresh_task = cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Ok(document_link_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                editor
+                    .visible_excerpts(true, cx)
+                    .into_values()
+                    .map(|(buffer, ..)| buffer)
+                    .filter(|editor_buffer| {
+                        let editor_buffer = editor_buffer.read(cx);
+                        let editor_buffer_id = editor_buffer.remote_id();
+                        let up_to_date = for_buffer.is_none()
+                            && editor
+                                .document_links
+                                .links
+                                .get(&editor_buffer_id)
+                                .is_some_and(|(version, _)| {
+                                    !editor_buffer.version().changed_since(version)
+                                });
+                        for_buffer.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                            && editor.registered_buffers.contains_key(&editor_buffer_id)
+                            && !up_to_date
+                    })
+                    .unique_by(|buffer| buffer.read(cx).remote_id())
+                    .map(|buffer| {
+                        let buffer_id = buffer.read(cx).remote_id();
+                        let version = buffer.read(cx).version();
+                        let document_links_task =
+                            project.update(cx, |project, cx| project.document_links(&buffer, cx));
+                        async move { (buffer_id, version, document_links_task.await) }
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let all_document_links = join_all(document_link_tasks).await;
+            editor
+                .update(cx, |editor, _| {
+                    for (buffer_id, version, document_links) in all_document_links {
+                        match document_links {
+                            Ok(document_links) => {
+                                editor
+                                    .document_links
+                                    .links
+                                    .insert(buffer_id, (version, document_links));
+                            }
+                            Err(e) => log::error!("Failed to fetch document links: {e:#}"),
+                        }
+                    }
+                })
+                .ok();
+        });
+    }
+
+    pub(super) fn document_link_at(
+        &self,
+        position: Anchor,
+        cx: &App,
+    ) -> Option<(Entity<Buffer>, DocumentLink)> {
+        let buffer_id = position.text_anchor.buffer_id?;
+        let (_, links) = self.document_links.links.get(&buffer_id)?;
+        let buffer = self.buffer.read(cx).buffer(buffer_id)?;
+        let snapshot = buffer.read(cx).snapshot();
+        let link = links.iter().find(|link| {
+            link.range
+                .start
+                .cmp(&position.text_anchor, &snapshot)
+                .is_le()
+                && link.range.end.cmp(&position.text_anchor, &snapshot).is_ge()
+        })?;
+        Some((buffer, link.clone()))
+    }
+}
+
+/// Links to files are opened in the editor, everything else is opened as a URL.
+pub(super) fn hover_link_for_target(target: &lsp::Uri) -> HoverLink {
+    match target.to_file_path() {
+        Ok(path) => HoverLink::File(ResolvedPath::AbsPath {
+            path: path.to_string_lossy().into_owned(),
+            is_dir: false,
+        }),
+        Err(_) => HoverLink::Url(target.to_string()),
+    }
+}
@@ -1,12 +1,15 @@
 use edit_prediction_types::EditPredictionDelegate;
 use gpui::{Entity, KeyBinding, Modifiers, prelude::*};
 use indoc::indoc;
+use languages::rust_lang;
 use multi_buffer::{Anchor, MultiBufferSnapshot, ToPoint};
 use std::{ops::Range, sync::Arc};
 use text::{Point, ToOffset};
+use theme::ActiveTheme as _;
 
 use crate::{
-    AcceptEditPrediction, EditPrediction, MenuEditPredictionsPolicy, editor_tests::init_test,
+    AcceptEditPrediction, EDIT_PREDICTION_SYNTAX_OPACITY, EditPrediction,
+    MenuEditPredictionsPolicy, editor_tests::init_test, inlays::InlayContent,
     test::editor_test_context::EditorTestContext,
 };
 
@@ -32,6 +35,46 @@ async fn test_edit_prediction_insert(cx: &mut gpui::TestAppContext) {
     cx.assert_editor_state("let absolute_zero_celsius = -273.15ˇ;")
 }
 
+#[gpui::test]
+async fn test_edit_prediction_syntax_highlighting(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings
+            .edit_predictions
+            .get_or_insert_default()
+            .syntax_highlighting = Some(true);
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = rust_lang();
+    cx.update(|_, cx| language.set_theme(cx.theme().syntax()));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    let provider = cx.new(|_| FakeEditPredictionDelegate::default());
+    assign_editor_completion_provider(provider.clone(), &mut cx);
+    cx.set_state("fn main() {ˇ}");
+    cx.run_until_parked();
+
+    propose_edits_with_preview(&provider, vec![(11..11, "let x = 1;")], &mut cx).await;
+    cx.update_editor(|editor, window, cx| editor.update_visible_edit_prediction(window, cx));
+
+    let keyword_color = cx.update(|_, cx| cx.theme().syntax().color("keyword"));
+    cx.editor(|editor, _, cx| {
+        let inlays = editor.all_inlays(cx);
+        assert_eq!(inlays.len(), 1);
+        let InlayContent::HighlightedText(text, highlights) = &inlays[0].content else {
+            panic!(
+                "expected a syntax highlighted prediction, got {:?}",
+                inlays[0].content
+            );
+        };
+        assert_eq!(text.to_string(), "let x = 1;");
+        assert!(
+            highlights.iter().any(|(range, style)| *range == (0..3)
+                && style.color == Some(keyword_color.opacity(EDIT_PREDICTION_SYNTAX_OPACITY))),
+            "expected `let` to be highlighted as a faded keyword, got {highlights:?}"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edit_prediction_modification(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -392,6 +435,35 @@ fn propose_edits<T: ToOffset>(
     });
 }
 
+async fn propose_edits_with_preview<T: ToOffset>(
+    provider: &Entity<FakeEditPredictionDelegate>,
+    edits: Vec<(Range<T>, &str)>,
+    cx: &mut EditorTestContext,
+) {
+    let snapshot = cx.buffer_snapshot();
+    let edits = edits
+        .into_iter()
+        .map(|(range, text)| {
+            let range = snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end);
+            (range, text.into())
+        })
+        .collect::<Arc<[_]>>();
+
+    let edit_preview = cx
+        .buffer(|buffer, cx| buffer.preview_edits(edits.clone(), cx))
+        .await;
+
+    cx.update(|_, cx| {
+        provider.update(cx, |provider, _| {
+            provider.set_edit_prediction(Some(edit_prediction_types::EditPrediction::Local {
+                id: None,
+                edits: edits.to_vec(),
+                edit_preview: Some(edit_preview),
+            }))
+        })
+    });
+}
+
 fn assign_editor_completion_provider(
     provider: Entity<FakeEditPredictionDelegate>,
     cx: &mut EditorTestContext,
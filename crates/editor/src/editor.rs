@@ -16,10 +16,13 @@ pub mod blink_manager;
 mod bracket_colorization;
 mod clangd_ext;
 pub mod code_context_menus;
+mod code_lens;
 pub mod display_map;
+mod document_links;
 mod editor_settings;
 mod element;
 mod git;
+mod hierarchy;
 mod highlight_matching_bracket;
 mod hover_links;
 pub mod hover_popover;
@@ -30,12 +33,14 @@ mod jsx_tag_auto_close;
 mod linked_editing_ranges;
 mod lsp_colors;
 mod lsp_ext;
+mod lsp_folding_ranges;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod shadowed_variables;
 mod split;
 pub mod tasks;
 
@@ -84,10 +89,12 @@ use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
     CompletionsMenu, ContextMenuOrigin,
 };
+use code_lens::CodeLensState;
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
 use dap::TelemetrySpawnLocation;
 use display_map::*;
+use document_links::DocumentLinks;
 use edit_prediction_types::{
     EditPredictionDelegate, EditPredictionDelegateHandle, EditPredictionGranularity,
 };
@@ -135,6 +142,7 @@ use lsp::{
     LanguageServerId,
 };
 use lsp_colors::LspColorData;
+use lsp_folding_ranges::LspFoldingRanges;
 use markdown::Markdown;
 use mouse_context_menu::MouseContextMenu;
 use movement::TextLayoutDetails;
@@ -236,9 +244,14 @@ pub(crate) const CODE_ACTION_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(1);
 pub const FETCH_COLORS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+pub const FETCH_CODE_LENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+pub const FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+pub const FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
 
 pub(crate) const EDIT_PREDICTION_KEY_CONTEXT: &str = "edit_prediction";
 pub(crate) const EDIT_PREDICTION_CONFLICT_KEY_CONTEXT: &str = "edit_prediction_conflict";
+/// Syntax colors of inline edit predictions are faded so they stay distinguishable from buffer text.
+const EDIT_PREDICTION_SYNTAX_OPACITY: f32 = 0.6;
 pub(crate) const MINIMAP_FONT_SIZE: AbsoluteLength = AbsoluteLength::Pixels(px(2.));
 
 pub type RenderDiffHunkControlsFn = Arc<
@@ -1207,6 +1220,9 @@ pub struct Editor {
     colors: Option<LspColorData>,
     post_scroll_update: Task<()>,
     refresh_colors_task: Task<()>,
+    code_lens: CodeLensState,
+    lsp_folding_ranges: LspFoldingRanges,
+    document_links: DocumentLinks,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -1975,7 +1991,7 @@ impl Editor {
                 window,
                 |editor, _, event, window, cx| match event {
                     project::Event::RefreshCodeLens => {
-                        // we always query lens with actions, without storing them, always refreshing them
+                        editor.refresh_code_lens(None, window, cx);
                     }
                     project::Event::RefreshInlayHints {
                         server_id,
@@ -2371,6 +2387,9 @@ impl Editor {
             pull_diagnostics_background_task: Task::ready(()),
             colors: None,
             refresh_colors_task: Task::ready(()),
+            code_lens: CodeLensState::new(cx),
+            lsp_folding_ranges: LspFoldingRanges::new(),
+            document_links: DocumentLinks::new(),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -2454,6 +2473,7 @@ impl Editor {
                                         cx,
                                     );
                                     editor.colorize_brackets(false, cx);
+                                    editor.highlight_shadowed_variables(cx);
                                 })
                                 .ok();
                         });
@@ -4597,7 +4617,7 @@ impl Editor {
 
         self.transact(window, cx, |this, window, cx| {
             if clear_linked_edit_ranges {
-                this.linked_edit_ranges.clear();
+                this.clear_linked_editing_ranges(cx);
             }
             let initial_buffer_versions =
                 jsx_tag_auto_close::construct_initial_buffer_versions_map(this, &edits, cx);
@@ -8148,13 +8168,37 @@ impl Editor {
                     .iter()
                     .all(|(range, _)| range.to_offset(&multibuffer).is_empty())
                 {
+                    let syntax_highlighting = all_language_settings(buffer.read(cx).file(), cx)
+                        .edit_predictions
+                        .syntax_highlighting;
                     let mut inlays = Vec::new();
                     for (range, new_text) in &edits {
-                        let inlay = Inlay::edit_prediction(
-                            post_inc(&mut self.next_inlay_id),
-                            range.start,
-                            new_text.as_ref(),
-                        );
+                        let id = post_inc(&mut self.next_inlay_id);
+                        let highlighted_text = edit_preview
+                            .as_ref()
+                            .filter(|_| syntax_highlighting)
+                            .map(|edit_preview| {
+                                edit_preview.highlight_inserted_text(
+                                    &(range.start.text_anchor..range.end.text_anchor),
+                                    new_text,
+                                    cx,
+                                )
+                            })
+                            // The preview was computed when the prediction arrived, so it no
+                            // longer matches edits that were interpolated with later typing.
+                            .filter(|highlighted_text| {
+                                highlighted_text.text.as_ref() == new_text.as_ref()
+                            });
+                        let inlay = if let Some(mut highlighted_text) = highlighted_text {
+                            for (_, style) in &mut highlighted_text.highlights {
+                                style.color = style
+                                    .color
+                                    .map(|color| color.opacity(EDIT_PREDICTION_SYNTAX_OPACITY));
+                            }
+                            Inlay::highlighted_edit_prediction(id, range.start, highlighted_text)
+                        } else {
+                            Inlay::edit_prediction(id, range.start, new_text.as_ref())
+                        };
                         inlay_ids.push(inlay.id);
                         inlays.push(inlay);
                     }
@@ -15781,6 +15825,100 @@ impl Editor {
         _: &SelectLargerSyntaxNode,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        let old_selections = self
+            .selections
+            .all::<MultiBufferOffset>(&self.display_snapshot(cx));
+        match self.lsp_selection_ranges(&old_selections, cx) {
+            Some(selection_ranges) => {
+                cx.spawn_in(window, async move |editor, cx| {
+                    let selection_ranges = selection_ranges.await;
+                    editor.update_in(cx, |editor, window, cx| {
+                        editor.select_larger_node(&selection_ranges, window, cx)
+                    })
+                })
+                .detach_and_log_err(cx);
+            }
+            None => self.select_larger_node(&[], window, cx),
+        }
+    }
+
+    /// Language servers know the semantic units of the language better than tree-sitter nodes
+    /// do, so their selection ranges are preferred when any of the buffers' servers provide them.
+    fn lsp_selection_ranges(
+        &self,
+        selections: &[Selection<MultiBufferOffset>],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Vec<Range<MultiBufferOffset>>>> {
+        let provider = self.semantics_provider.clone()?;
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut positions_by_excerpt =
+            HashMap::<ExcerptId, (Entity<Buffer>, Vec<text::Anchor>)>::default();
+        for selection in selections {
+            let excerpt_id = multi_buffer_snapshot
+                .anchor_before(selection.start)
+                .excerpt_id;
+            let Some((buffer, position)) = self
+                .buffer
+                .read(cx)
+                .text_anchor_for_position(selection.start, cx)
+            else {
+                continue;
+            };
+            positions_by_excerpt
+                .entry(excerpt_id)
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push(position);
+        }
+        if positions_by_excerpt.is_empty() {
+            return None;
+        }
+
+        let mut selection_range_tasks = Vec::new();
+        for (excerpt_id, (buffer, positions)) in positions_by_excerpt {
+            let selection_ranges = provider.selection_ranges(&buffer, positions, cx)?;
+            selection_range_tasks.push(async move { (excerpt_id, selection_ranges.await) });
+        }
+        Some(cx.spawn(async move |editor, cx| {
+            let all_selection_ranges = future::join_all(selection_range_tasks).await;
+            editor
+                .read_with(cx, |editor, cx| {
+                    let multi_buffer_snapshot = editor.buffer.read(cx).snapshot(cx);
+                    all_selection_ranges
+                        .into_iter()
+                        .filter_map(|(excerpt_id, selection_ranges)| {
+                            Some((excerpt_id, selection_ranges.log_err()?))
+                        })
+                        .flat_map(|(excerpt_id, selection_ranges)| {
+                            selection_ranges
+                                .into_iter()
+                                .flatten()
+                                .map(move |range| (excerpt_id, range))
+                        })
+                        .filter_map(|(excerpt_id, range)| {
+                            let start =
+                                multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, range.start)?;
+                            let end =
+                                multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, range.end)?;
+                            Some(
+                                start.to_offset(&multi_buffer_snapshot)
+                                    ..end.to_offset(&multi_buffer_snapshot),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }))
+    }
+
+    /// Grows every selection to the smallest of the `lsp_selection_ranges` strictly containing it,
+    /// or to its syntax node parent if there is none.
+    fn select_larger_node(
+        &mut self,
+        lsp_selection_ranges: &[Range<MultiBufferOffset>],
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let Some(visible_row_count) = self.visible_row_count() else {
             return;
@@ -15804,6 +15942,25 @@ impl Editor {
             .map(|selection| {
                 let old_range = selection.start..selection.end;
 
+                if let Some(lsp_range) = lsp_selection_ranges
+                    .iter()
+                    .filter(|range| {
+                        range.start <= old_range.start
+                            && old_range.end <= range.end
+                            && **range != old_range
+                    })
+                    .min_by_key(|range| range.end - range.start)
+                {
+                    selected_larger_node = true;
+                    return Selection {
+                        id: selection.id,
+                        start: lsp_range.start,
+                        end: lsp_range.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    };
+                }
+
                 if let Some((node, _)) = buffer.syntax_ancestor(old_range.clone()) {
                     // manually select word at selection
                     if ["string_content", "inline"].contains(&node.kind()) {
@@ -21901,6 +22058,7 @@ impl Editor {
                 self.update_lsp_data(Some(buffer_id), window, cx);
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                 self.colorize_brackets(false, cx);
+                self.highlight_shadowed_variables(cx);
                 cx.emit(EditorEvent::ExcerptsAdded {
                     buffer: buffer.clone(),
                     predecessor: *predecessor,
@@ -21942,12 +22100,14 @@ impl Editor {
                     self.fetched_tree_sitter_chunks.remove(id);
                 }
                 self.colorize_brackets(false, cx);
+                self.highlight_shadowed_variables(cx);
                 cx.emit(EditorEvent::ExcerptsExpanded { ids: ids.clone() })
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
                 self.tasks_update_task = Some(self.refresh_runnables(window, cx));
                 self.refresh_selected_text_highlights(true, window, cx);
                 self.colorize_brackets(true, cx);
+                self.highlight_shadowed_variables(cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
 
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
@@ -22157,6 +22317,11 @@ impl Editor {
 
             if language_settings_changed || accents_changed {
                 self.colorize_brackets(true, cx);
+                self.highlight_shadowed_variables(cx);
+            }
+
+            if language_settings_changed {
+                self.refresh_lsp_folding_ranges(None, window, cx);
             }
 
             if let Some(inlay_splice) = self.colors.as_mut().and_then(|colors| {
@@ -22167,6 +22332,13 @@ impl Editor {
                 }
                 self.refresh_colors_for_visible_range(None, window, cx);
             }
+
+            if self
+                .code_lens
+                .enabled_updated(EditorSettings::get_global(cx).lsp_code_lens)
+            {
+                self.refresh_code_lens(None, window, cx);
+            }
         }
 
         cx.notify();
@@ -23035,6 +23207,9 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors_for_visible_range(for_buffer, window, cx);
+        self.refresh_code_lens(for_buffer, window, cx);
+        self.refresh_lsp_folding_ranges(for_buffer, window, cx);
+        self.refresh_document_links(for_buffer, window, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -24046,6 +24221,14 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<Option<Range<text::Anchor>>>>>;
 
+    /// For every position, the ranges containing it from the innermost to the outermost.
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
+
     fn perform_rename(
         &self,
         buffer: &Entity<Buffer>,
@@ -24637,6 +24820,17 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        self.update(cx, |project, cx| {
+            project.selection_ranges(buffer, positions, cx)
+        })
+    }
 }
 
 fn consume_contiguous_rows(
@@ -4,7 +4,7 @@ use crate::{
     code_context_menus::CodeContextMenu,
     edit_prediction_tests::FakeEditPredictionDelegate,
     element::StickyHeader,
-    linked_editing_ranges::LinkedEditingRanges,
+    linked_editing_ranges::{self, LinkedEditingRanges},
     scroll::scroll_amount::ScrollAmount,
     test::{
         assert_text_with_selections, build_editor,
@@ -48,9 +48,9 @@ use project::{
 };
 use serde_json::{self, json};
 use settings::{
-    AllLanguageSettingsContent, EditorSettingsContent, IndentGuideBackgroundColoring,
-    IndentGuideColoring, InlayHintSettingsContent, ProjectSettingsContent, SearchSettingsContent,
-    SettingsStore,
+    AllLanguageSettingsContent, EditorSettingsContent, FoldingProvider,
+    IndentGuideBackgroundColoring, IndentGuideColoring, InlayHintSettingsContent,
+    ProjectSettingsContent, SearchSettingsContent, SettingsStore,
 };
 use std::{cell::RefCell, future::Future, rc::Rc, sync::atomic::AtomicBool, time::Instant};
 use std::{
@@ -9677,6 +9677,72 @@ async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppConte
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_smaller_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state(indoc! {"
+        fn main() {
+            foo(baˇr, baz);
+        }
+    "});
+    cx.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(|_, _, _| async move {
+        let range = |start: u32, end: u32| lsp::Range {
+            start: lsp::Position::new(1, start),
+            end: lsp::Position::new(1, end),
+        };
+        Ok(Some(vec![lsp::SelectionRange {
+            range: range(8, 11),
+            parent: Some(Box::new(lsp::SelectionRange {
+                range: range(8, 16),
+                parent: Some(Box::new(lsp::SelectionRange {
+                    range: range(4, 17),
+                    parent: None,
+                })),
+            })),
+        }]))
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            foo(«barˇ», baz);
+        }
+    "});
+
+    // The arguments are no syntax node of their own, but the server treats them as a unit.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            foo(«bar, bazˇ»);
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            foo(«barˇ», baz);
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_unwrap_syntax_nodes(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -18269,6 +18335,49 @@ async fn test_on_type_formatting_is_applied_after_autoindent(cx: &mut TestAppCon
     assert!(request.next().await.is_none());
 }
 
+#[gpui::test]
+async fn test_on_type_formatting_discarded_after_new_input(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            document_on_type_formatting_provider: Some(lsp::DocumentOnTypeFormattingOptions {
+                first_trigger_character: ";".to_string(),
+                more_trigger_character: None,
+            }),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state("fn main() {\n    let a = 5ˇ\n}\n");
+
+    let buffer_cloned = cx.multibuffer(|multi_buffer, _| multi_buffer.as_singleton().unwrap());
+    let mut request =
+        cx.set_request_handler::<lsp::request::OnTypeFormatting, _, _>(move |_, _, mut cx| {
+            let buffer_cloned = buffer_cloned.clone();
+            async move {
+                // The user keeps typing while the server computes the formatting.
+                buffer_cloned.update(&mut cx, |buffer, cx| {
+                    let end = buffer.len();
+                    buffer.edit([(end..end, "// c")], None, cx);
+                })?;
+
+                Ok(Some(vec![lsp::TextEdit {
+                    new_text: " ".to_string(),
+                    range: lsp::Range::new(lsp::Position::new(1, 12), lsp::Position::new(1, 13)),
+                }]))
+            }
+        });
+
+    cx.simulate_keystroke(";");
+    assert!(request.next().await.is_some());
+    cx.run_until_parked();
+
+    cx.assert_editor_state("fn main() {\n    let a = 5;ˇ\n}\n// c");
+}
+
 #[gpui::test]
 async fn test_language_server_restart_due_to_settings_change(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -22952,6 +23061,226 @@ async fn test_find_all_references_editor_reuse(cx: &mut TestAppContext) {
         );
     });
 }
+
+#[gpui::test]
+async fn test_find_incoming_and_outgoing_calls(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state(
+        &r#"
+        fn one() {
+            two();
+        }
+
+        fn ˇtwo() {
+            three();
+        }
+
+        fn three() {}"#
+            .unindent(),
+    );
+    let url = cx.buffer_lsp_url.clone();
+    let item = |name: &str, line: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: url.clone(),
+        range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line + 2, 1)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(line, 3),
+            lsp::Position::new(line, 3 + name.len() as u32),
+        ),
+        data: None,
+    };
+    let two = item("two", 4);
+    let one = item("one", 0);
+    let three = item("three", 8);
+    cx.lsp
+        .set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(move |_, _| {
+            let two = two.clone();
+            async move { Ok(Some(vec![two])) }
+        });
+    cx.lsp
+        .set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(move |params, _| {
+            let one = one.clone();
+            async move {
+                assert_eq!(params.item.name, "two");
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: one,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(1, 4),
+                        lsp::Position::new(1, 7),
+                    )],
+                }]))
+            }
+        });
+    cx.lsp
+        .set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(move |params, _| {
+            let three = three.clone();
+            async move {
+                assert_eq!(params.item.name, "two");
+                Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                    to: three,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(5, 4),
+                        lsp::Position::new(5, 9),
+                    )],
+                }]))
+            }
+        });
+
+    let navigated = cx
+        .update_editor(|editor, window, cx| {
+            editor.find_incoming_calls(&FindIncomingCalls, window, cx)
+        })
+        .unwrap()
+        .await
+        .expect("Failed to find incoming calls");
+    assert_eq!(navigated, Navigated::Yes);
+    cx.update_workspace(|workspace, _, cx| {
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "Incoming Calls to two");
+        assert_eq!(
+            newest_selection_in_buffer(active_editor.read(cx), cx),
+            Point::new(1, 4)..Point::new(1, 7),
+            "incoming calls should point at the call sites within the caller"
+        );
+        assert!(
+            active_editor
+                .read(cx)
+                .text(cx)
+                .contains("fn one() {\n    two();\n}")
+        );
+    });
+
+    let navigated = cx
+        .update_editor(|editor, window, cx| {
+            editor.find_outgoing_calls(&FindOutgoingCalls, window, cx)
+        })
+        .unwrap()
+        .await
+        .expect("Failed to find outgoing calls");
+    assert_eq!(navigated, Navigated::Yes);
+    cx.update_workspace(|workspace, _, cx| {
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "Outgoing Calls from two");
+        assert_eq!(
+            newest_selection_in_buffer(active_editor.read(cx), cx),
+            Point::new(8, 3)..Point::new(8, 8),
+            "outgoing calls should point at the callees"
+        );
+        assert!(active_editor.read(cx).text(cx).contains("fn three() {}"));
+    });
+}
+
+fn newest_selection_in_buffer(editor: &Editor, cx: &App) -> Range<Point> {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let selection = editor.selections.newest_anchor();
+    let buffer = snapshot
+        .buffer_for_excerpt(selection.start.excerpt_id)
+        .expect("selection should be within an excerpt");
+    selection.start.text_anchor.to_point(buffer)..selection.end.text_anchor.to_point(buffer)
+}
+
+#[gpui::test]
+async fn test_find_supertypes_and_subtypes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        serde_json::from_value(json!({ "typeHierarchyProvider": {} })).unwrap(),
+        cx,
+    )
+    .await;
+
+    cx.set_state(
+        &r#"
+        trait One {}
+
+        trait ˇTwo: One {}
+
+        struct Three;
+        impl Two for Three {}"#
+            .unindent(),
+    );
+    let url = cx.buffer_lsp_url.clone();
+    let item = |name: &str, kind: lsp::SymbolKind, line: u32, column: u32| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail: None,
+        uri: url.clone(),
+        range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line + 1, 0)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(line, column),
+            lsp::Position::new(line, column + name.len() as u32),
+        ),
+        data: None,
+    };
+    let one = item("One", lsp::SymbolKind::INTERFACE, 0, 6);
+    let two = item("Two", lsp::SymbolKind::INTERFACE, 2, 6);
+    let three = item("Three", lsp::SymbolKind::STRUCT, 4, 7);
+    cx.lsp
+        .set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(move |_, _| {
+            let two = two.clone();
+            async move { Ok(Some(vec![two])) }
+        });
+    cx.lsp
+        .set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(move |params, _| {
+            let one = one.clone();
+            async move {
+                assert_eq!(params.item.name, "Two");
+                Ok(Some(vec![one]))
+            }
+        });
+    cx.lsp
+        .set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(move |params, _| {
+            let three = three.clone();
+            async move {
+                assert_eq!(params.item.name, "Two");
+                Ok(Some(vec![three]))
+            }
+        });
+
+    let navigated = cx
+        .update_editor(|editor, window, cx| editor.find_supertypes(&FindSupertypes, window, cx))
+        .unwrap()
+        .await
+        .expect("Failed to find supertypes");
+    assert_eq!(navigated, Navigated::Yes);
+    cx.update_workspace(|workspace, _, cx| {
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "Supertypes of Two");
+        assert_eq!(
+            newest_selection_in_buffer(active_editor.read(cx), cx),
+            Point::new(0, 6)..Point::new(0, 9),
+        );
+    });
+
+    let navigated = cx
+        .update_editor(|editor, window, cx| editor.find_subtypes(&FindSubtypes, window, cx))
+        .unwrap()
+        .await
+        .expect("Failed to find subtypes");
+    assert_eq!(navigated, Navigated::Yes);
+    cx.update_workspace(|workspace, _, cx| {
+        let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "Subtypes of Two");
+        assert_eq!(
+            newest_selection_in_buffer(active_editor.read(cx), cx),
+            Point::new(4, 7)..Point::new(4, 12),
+        );
+        assert!(active_editor.read(cx).text(cx).contains("struct Three;"));
+    });
+}
+
 #[gpui::test]
 async fn test_find_enclosing_node_with_task(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -25403,6 +25732,52 @@ async fn test_linked_edits_on_typing_punctuation(cx: &mut TestAppContext) {
     cx.assert_editor_state("<Animated.Vˇ></Animated.V>");
 }
 
+#[gpui::test]
+async fn test_linked_edit_ranges_highlights(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    fn highlight_tag_names(editor: &mut Editor, closing_tag_start: u32, cx: &mut Context<Editor>) {
+        let buffer = editor.buffer.read(cx).as_singleton().unwrap();
+        let buffer = buffer.read(cx);
+        let buffer_id = buffer.remote_id();
+        let opening_range =
+            buffer.anchor_before(Point::new(0, 1))..buffer.anchor_after(Point::new(0, 4));
+        let closing_range = buffer.anchor_before(Point::new(0, closing_tag_start))
+            ..buffer.anchor_after(Point::new(0, closing_tag_start + 3));
+        let mut linked_ranges = HashMap::default();
+        linked_ranges.insert(
+            buffer_id,
+            vec![
+                (opening_range.clone(), vec![closing_range.clone()]),
+                (closing_range, vec![opening_range]),
+            ],
+        );
+        editor.linked_edit_ranges = LinkedEditingRanges(linked_ranges);
+        editor.highlight_linked_editing_ranges(cx);
+    }
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("<divˇ></div>");
+    cx.update_editor(|editor, _, cx| highlight_tag_names(editor, 7, cx));
+    cx.assert_editor_background_highlights::<LinkedEditingRanges>("<«div»></«div»>");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.handle_input(">", window, cx);
+    });
+    cx.assert_editor_state("<div>ˇ></div>");
+    cx.assert_editor_background_highlights::<LinkedEditingRanges>("<div>></div>");
+
+    // Without a language server to report the ranges anew, the refresh drops the stale ones.
+    cx.update_editor(|editor, _, cx| highlight_tag_names(editor, 8, cx));
+    cx.assert_editor_background_highlights::<LinkedEditingRanges>("<«div»>></«div»>");
+    cx.update_editor(|editor, window, cx| {
+        linked_editing_ranges::refresh_linked_ranges(editor, window, cx);
+    });
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<LinkedEditingRanges>("<div>></div>");
+}
+
 #[gpui::test]
 async fn test_invisible_worktree_servers(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -27618,6 +27993,508 @@ async fn test_document_colors(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_document_color_presentations(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "first.rs": "fn main() { let a = 5; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                color_provider: Some(lsp::ColorProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/first.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_language_server = fake_servers.next().await.unwrap();
+    let color_range = lsp::Range {
+        start: lsp::Position {
+            line: 0,
+            character: 20,
+        },
+        end: lsp::Position {
+            line: 0,
+            character: 21,
+        },
+    };
+    let mut color_request_handle = fake_language_server
+        .set_request_handler::<lsp::request::DocumentColor, _, _>(move |_, _| async move {
+            Ok(vec![lsp::ColorInformation {
+                range: color_range,
+                color: lsp::Color {
+                    red: 1.0,
+                    green: 0.0,
+                    blue: 0.0,
+                    alpha: 1.0,
+                },
+            }])
+        });
+    let mut presentation_request_handle = fake_language_server
+        .set_request_handler::<lsp::request::ColorPresentationRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.range, color_range);
+            Ok(vec![
+                lsp::ColorPresentation {
+                    label: "7".to_string(),
+                    text_edit: None,
+                    additional_text_edits: None,
+                },
+                lsp::ColorPresentation {
+                    label: "8".to_string(),
+                    text_edit: Some(lsp::TextEdit {
+                        range: color_range,
+                        new_text: "8".to_string(),
+                    }),
+                    additional_text_edits: Some(vec![lsp::TextEdit {
+                        range: lsp::Range {
+                            start: lsp::Position {
+                                line: 0,
+                                character: 0,
+                            },
+                            end: lsp::Position {
+                                line: 0,
+                                character: 0,
+                            },
+                        },
+                        new_text: "// red\n".to_string(),
+                    }]),
+                },
+            ])
+        },
+    );
+    cx.executor().advance_clock(FETCH_COLORS_DEBOUNCE_TIMEOUT);
+    color_request_handle.next().await.unwrap();
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 20)..Point::new(0, 20)])
+        });
+        editor.show_color_presentations(&ShowColorPresentations, window, cx);
+    });
+    presentation_request_handle.next().await.unwrap();
+    cx.run_until_parked();
+
+    let context_menu = editor.update(cx, |editor, _| {
+        editor
+            .mouse_context_menu
+            .as_ref()
+            .map(|menu| menu.context_menu.clone())
+            .expect("Should show the color presentations menu")
+    });
+    context_menu.update_in(cx, |context_menu, window, cx| {
+        context_menu.select_first(&menu::SelectFirst, window, cx);
+        context_menu.confirm(&menu::Confirm, window, cx);
+    });
+    cx.run_until_parked();
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.text(cx),
+            "fn main() { let a = 7; }",
+            "Presentations without an edit should replace the color range with their label"
+        );
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 20)..Point::new(0, 20)])
+        });
+        editor.show_color_presentations(&ShowColorPresentations, window, cx);
+    });
+    presentation_request_handle.next().await.unwrap();
+    cx.run_until_parked();
+
+    let context_menu = editor.update(cx, |editor, _| {
+        editor
+            .mouse_context_menu
+            .as_ref()
+            .map(|menu| menu.context_menu.clone())
+            .expect("Should show the color presentations menu again")
+    });
+    editor.update(cx, |editor, cx| {
+        editor.edit([(Point::new(0, 12)..Point::new(0, 12), "let b = 1; ")], cx);
+    });
+    context_menu.update_in(cx, |context_menu, window, cx| {
+        context_menu.select_first(&menu::SelectFirst, window, cx);
+        context_menu.select_next(&menu::SelectNext, window, cx);
+        context_menu.confirm(&menu::Confirm, window, cx);
+    });
+    cx.run_until_parked();
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.text(cx),
+            "// red\nfn main() { let b = 1; let a = 8; }",
+            "The presentation edit and its additional edits should apply where the server meant them, \
+            even after the buffer was edited while the menu was open"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_code_lens_blocks(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    update_test_editor_settings(cx, |settings| {
+        settings.lsp_code_lens = Some(true);
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "first.rs": "fn main() {\n    run();\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_lens_provider: Some(lsp::CodeLensOptions {
+                    resolve_provider: None,
+                }),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec!["run".to_string()],
+                    ..lsp::ExecuteCommandOptions::default()
+                }),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/first.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_language_server = fake_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::CodeLensRequest, _, _>(
+        |_, _| async move {
+            Ok(Some(vec![
+                lsp::CodeLens {
+                    range: lsp::Range {
+                        start: lsp::Position::new(0, 3),
+                        end: lsp::Position::new(0, 7),
+                    },
+                    command: Some(lsp::Command {
+                        title: "Run".to_owned(),
+                        command: "run".to_owned(),
+                        arguments: None,
+                    }),
+                    data: None,
+                },
+                lsp::CodeLens {
+                    range: lsp::Range {
+                        start: lsp::Position::new(0, 3),
+                        end: lsp::Position::new(0, 7),
+                    },
+                    command: Some(lsp::Command {
+                        title: "Not in capabilities".to_owned(),
+                        command: "not in capabilities".to_owned(),
+                        arguments: None,
+                    }),
+                    data: None,
+                },
+                lsp::CodeLens {
+                    range: lsp::Range {
+                        start: lsp::Position::new(1, 4),
+                        end: lsp::Position::new(1, 7),
+                    },
+                    command: None,
+                    data: None,
+                },
+            ]))
+        },
+    );
+    cx.executor()
+        .advance_clock(FETCH_CODE_LENS_DEBOUNCE_TIMEOUT + Duration::from_millis(100));
+    cx.run_until_parked();
+
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.display_text(cx),
+            "\nfn main() {\n    run();\n}",
+            "Only the lens with a known command should be shown, above its line"
+        );
+    });
+
+    update_test_editor_settings(cx, |settings| {
+        settings.lsp_code_lens = Some(false);
+    });
+    cx.run_until_parked();
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.display_text(cx),
+            "fn main() {\n    run();\n}",
+            "Lens blocks should be removed when the setting is disabled"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_code_lens_resolve(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    update_test_editor_settings(cx, |settings| {
+        settings.lsp_code_lens = Some(true);
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "first.rs": "fn main() {\n    run();\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_lens_provider: Some(lsp::CodeLensOptions {
+                    resolve_provider: Some(true),
+                }),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec!["run".to_string()],
+                    ..lsp::ExecuteCommandOptions::default()
+                }),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/first.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_language_server = fake_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::CodeLensRequest, _, _>(
+        |_, _| async move {
+            Ok(Some(vec![lsp::CodeLens {
+                range: lsp::Range {
+                    start: lsp::Position::new(0, 3),
+                    end: lsp::Position::new(0, 7),
+                },
+                command: None,
+                data: Some(json!("main")),
+            }]))
+        },
+    );
+    let resolve_requests = Arc::new(AtomicUsize::new(0));
+    fake_language_server.set_request_handler::<lsp::request::CodeLensResolve, _, _>({
+        let resolve_requests = resolve_requests.clone();
+        move |lens, _| {
+            resolve_requests.fetch_add(1, atomic::Ordering::Release);
+            async move {
+                assert_eq!(lens.data, Some(json!("main")));
+                Ok(lsp::CodeLens {
+                    command: Some(lsp::Command {
+                        title: "Run".to_owned(),
+                        command: "run".to_owned(),
+                        arguments: None,
+                    }),
+                    ..lens
+                })
+            }
+        }
+    });
+    cx.executor()
+        .advance_clock(FETCH_CODE_LENS_DEBOUNCE_TIMEOUT + Duration::from_millis(100));
+    cx.run_until_parked();
+
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.display_text(cx),
+            "\nfn main() {\n    run();\n}",
+            "The lens should be shown once it is resolved"
+        );
+    });
+    assert_eq!(resolve_requests.load(atomic::Ordering::Acquire), 1);
+
+    update_test_editor_settings(cx, |settings| {
+        settings.lsp_code_lens = Some(false);
+    });
+    cx.run_until_parked();
+    update_test_editor_settings(cx, |settings| {
+        settings.lsp_code_lens = Some(true);
+    });
+    cx.executor()
+        .advance_clock(FETCH_CODE_LENS_DEBOUNCE_TIMEOUT + Duration::from_millis(100));
+    cx.run_until_parked();
+    editor.update(cx, |editor, cx| {
+        assert_eq!(editor.display_text(cx), "\nfn main() {\n    run();\n}");
+    });
+    assert_eq!(
+        resolve_requests.load(atomic::Ordering::Acquire),
+        1,
+        "Resolved lenses should be cached"
+    );
+}
+
+#[gpui::test]
+async fn test_lsp_folding_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    update_test_language_settings(cx, |settings| {
+        settings.defaults.folding_provider = Some(FoldingProvider::LanguageServer);
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "first.rs": "// first\n// second\n// third\nfn main() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/first.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_language_server = fake_servers.next().await.unwrap();
+    fake_language_server.set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(
+        |_, _| async move {
+            Ok(Some(vec![lsp::FoldingRange {
+                start_line: 0,
+                start_character: None,
+                end_line: 2,
+                end_character: None,
+                kind: Some(lsp::FoldingRangeKind::Comment),
+                collapsed_text: None,
+            }]))
+        },
+    );
+    cx.executor()
+        .advance_clock(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+        editor.fold(&Fold, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "// first⋯\nfn main() {}",
+            "Comment lines have no indentation to fold by, so the server range should be used"
+        );
+    });
+
+    let buffer = editor.update(cx, |editor, cx| {
+        editor
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .expect("should be a singleton buffer")
+    });
+    let folding_ranges = project.update(cx, |project, cx| {
+        let folding_ranges = project.folding_ranges(&buffer, cx);
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "// zeroth\n")], None, cx);
+        });
+        folding_ranges
+    });
+    assert!(
+        folding_ranges.await.unwrap().is_none(),
+        "Ranges computed for an older buffer version should be discarded"
+    );
+}
+
 #[gpui::test]
 async fn test_newline_replacement_in_single_line(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -0,0 +1,149 @@
+use std::{cmp::Reverse, collections::HashMap, ops::Range};
+
+use anyhow::Result;
+use gpui::{Entity, Task};
+use itertools::Itertools as _;
+use language::{Buffer, Point};
+use multi_buffer::{MultiBufferOffset, MultiBufferSnapshot, ToOffset as _};
+use project::{Location, Project};
+use settings::Settings as _;
+use text::OffsetRangeExt as _;
+use ui::{App, Context, Window};
+use workspace::item::PreviewTabsSettings;
+
+use crate::{
+    Editor, FindIncomingCalls, FindOutgoingCalls, FindSubtypes, FindSupertypes,
+    MultibufferSelectionMode, Navigated,
+};
+
+impl Editor {
+    pub fn find_incoming_calls(
+        &mut self,
+        _: &FindIncomingCalls,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Navigated>>> {
+        self.open_hierarchy_locations(
+            |symbol| format!("Incoming Calls to {symbol}"),
+            |project, buffer, position, cx| project.incoming_calls(buffer, position, cx),
+            window,
+            cx,
+        )
+    }
+
+    pub fn find_outgoing_calls(
+        &mut self,
+        _: &FindOutgoingCalls,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Navigated>>> {
+        self.open_hierarchy_locations(
+            |symbol| format!("Outgoing Calls from {symbol}"),
+            |project, buffer, position, cx| project.outgoing_calls(buffer, position, cx),
+            window,
+            cx,
+        )
+    }
+
+    pub fn find_supertypes(
+        &mut self,
+        _: &FindSupertypes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Navigated>>> {
+        self.open_hierarchy_locations(
+            |symbol| format!("Supertypes of {symbol}"),
+            |project, buffer, position, cx| project.supertypes(buffer, position, cx),
+            window,
+            cx,
+        )
+    }
+
+    pub fn find_subtypes(
+        &mut self,
+        _: &FindSubtypes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Navigated>>> {
+        self.open_hierarchy_locations(
+            |symbol| format!("Subtypes of {symbol}"),
+            |project, buffer, position, cx| project.subtypes(buffer, position, cx),
+            window,
+            cx,
+        )
+    }
+
+    /// Queries the hierarchy of the symbol under the newest selection and opens the related
+    /// locations in a multibuffer, the same way as "Find All References" does.
+    fn open_hierarchy_locations(
+        &mut self,
+        title: fn(&str) -> String,
+        query: fn(
+            &mut Project,
+            &Entity<Buffer>,
+            text::Anchor,
+            &mut Context<Project>,
+        ) -> Task<Result<Vec<Location>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Navigated>>> {
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let head = self
+            .selections
+            .newest_anchor()
+            .head()
+            .to_offset(&multi_buffer_snapshot);
+        let (buffer, position) = self.buffer.read(cx).text_anchor_for_position(head, cx)?;
+        let symbol = symbol_at(&multi_buffer_snapshot, head);
+        let workspace = self.workspace()?;
+        let project = workspace.read(cx).project().clone();
+        let locations = project.update(cx, |project, cx| query(project, &buffer, position, cx));
+        Some(cx.spawn_in(window, async move |_, cx| {
+            let locations = locations.await?;
+            let mut locations = cx.update(|_, cx| group_locations(locations, cx))?;
+            if locations.is_empty() {
+                return Ok(Navigated::No);
+            }
+            for ranges in locations.values_mut() {
+                ranges.sort_by_key(|range| (range.start, Reverse(range.end)));
+                ranges.dedup();
+            }
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let allow_preview = PreviewTabsSettings::get_global(cx)
+                    .enable_preview_multibuffer_from_code_navigation;
+                Self::open_locations_in_multibuffer(
+                    workspace,
+                    locations,
+                    title(&symbol),
+                    false,
+                    allow_preview,
+                    MultibufferSelectionMode::First,
+                    window,
+                    cx,
+                );
+                Navigated::Yes
+            })
+        }))
+    }
+}
+
+fn symbol_at(multi_buffer_snapshot: &MultiBufferSnapshot, offset: MultiBufferOffset) -> String {
+    let (word_range, _) = multi_buffer_snapshot.surrounding_word(offset, None);
+    multi_buffer_snapshot
+        .text_for_range(word_range)
+        .collect::<String>()
+}
+
+fn group_locations(
+    locations: Vec<Location>,
+    cx: &App,
+) -> HashMap<Entity<Buffer>, Vec<Range<Point>>> {
+    locations
+        .into_iter()
+        .map(|location| {
+            let range = location.range.to_point(location.buffer.read(cx));
+            (location.buffer, range)
+        })
+        .into_group_map()
+}
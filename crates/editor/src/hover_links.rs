@@ -1,8 +1,8 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToDefinitionSplit, GoToTypeDefinition, GoToTypeDefinitionSplit, GotoDefinitionKind,
-    Navigated, PointForPosition, SelectPhase, editor_settings::GoToDefinitionFallback,
-    scroll::ScrollAmount,
+    Navigated, PointForPosition, SelectPhase, document_links::hover_link_for_target,
+    editor_settings::GoToDefinitionFallback, scroll::ScrollAmount,
 };
 use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
 use language::{Bias, ToOffset};
@@ -346,13 +346,36 @@ pub fn show_link_definition(
     }
     let project = editor.project.clone();
     let provider = editor.semantics_provider.clone();
+    let document_link = match &trigger_point {
+        TriggerPoint::Text(_) => editor.document_link_at(anchor, cx),
+        TriggerPoint::InlayHint(..) => None,
+    };
 
     let snapshot = snapshot.buffer_snapshot().clone();
     hovered_link_state.task = Some(cx.spawn_in(window, async move |this, cx| {
         async move {
+            let document_link = match (document_link, &project) {
+                (Some((link_buffer, document_link)), Some(project)) => {
+                    let resolve_task = project.update(cx, |project, cx| {
+                        project.resolve_document_link(&link_buffer, document_link.clone(), cx)
+                    })?;
+                    let document_link = resolve_task.await.log_err().unwrap_or(document_link);
+                    document_link.lsp_link.target.as_ref().map(|target| {
+                        let range = snapshot
+                            .anchor_range_in_excerpt(excerpt_id, document_link.range.clone())
+                            .map(RangeInEditor::Text);
+                        (range, vec![hover_link_for_target(target)])
+                    })
+                }
+                _ => None,
+            };
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, text_anchor, cx.clone()) {
+                    if let Some(document_link) = document_link {
+                        Some(document_link)
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, text_anchor, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let range =
@@ -1306,6 +1329,62 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        let mut document_link_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(|_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: lsp::Range::new(lsp::Position::new(0, 4), lsp::Position::new(0, 9)),
+                    target: None,
+                    tooltip: None,
+                    data: None,
+                }]))
+            });
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                |link, _, _| async move {
+                    Ok(lsp::DocumentLink {
+                        target: Some("https://docs.rs/serde".parse().unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        cx.set_state(indoc! {"
+            use serde;ˇ
+        "});
+        cx.executor()
+            .advance_clock(crate::FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT);
+        document_link_requests.next().await;
+        cx.run_until_parked();
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use seˇrde;
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        resolve_requests.next().await;
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            use «serdeˇ»;
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+    }
+
     #[gpui::test]
     async fn test_urls_at_beginning_of_buffer(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -17,9 +17,14 @@
 /// Logic, related to managing LSP inlay hint inlays.
 pub mod inlay_hints;
 
-use std::{any::TypeId, sync::OnceLock};
+use std::{
+    any::TypeId,
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
 
 use gpui::{Context, HighlightStyle, Hsla, Rgba, Task};
+use language::HighlightedText;
 use multi_buffer::Anchor;
 use project::{InlayHint, InlayId};
 use text::Rope;
@@ -52,6 +57,8 @@ pub struct Inlay {
 #[derive(Debug, Clone)]
 pub enum InlayContent {
     Text(text::Rope),
+    /// Text with its own styles, keyed by byte ranges in the text.
+    HighlightedText(text::Rope, Arc<[(Range<usize>, HighlightStyle)]>),
     Color(Hsla),
 }
 
@@ -96,6 +103,21 @@ impl Inlay {
         }
     }
 
+    pub fn highlighted_edit_prediction(
+        id: usize,
+        position: Anchor,
+        highlighted_text: HighlightedText,
+    ) -> Self {
+        Self {
+            id: InlayId::EditPrediction(id),
+            position,
+            content: InlayContent::HighlightedText(
+                Rope::from(highlighted_text.text.as_ref()),
+                highlighted_text.highlights.into(),
+            ),
+        }
+    }
+
     pub fn debugger<T: Into<Rope>>(id: usize, position: Anchor, text: T) -> Self {
         Self {
             id: InlayId::DebuggerValue(id),
@@ -107,7 +129,7 @@ impl Inlay {
     pub fn text(&self) -> &Rope {
         static COLOR_TEXT: OnceLock<Rope> = OnceLock::new();
         match &self.content {
-            InlayContent::Text(text) => text,
+            InlayContent::Text(text) | InlayContent::HighlightedText(text, _) => text,
             InlayContent::Color(_) => COLOR_TEXT.get_or_init(|| Rope::from("◼")),
         }
     }
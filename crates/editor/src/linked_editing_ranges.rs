@@ -1,7 +1,7 @@
 use collections::HashMap;
 use gpui::{AppContext, Context, Window};
 use itertools::Itertools;
-use multi_buffer::MultiBufferOffset;
+use multi_buffer::{Anchor, MultiBufferOffset};
 use std::{ops::Range, time::Duration};
 use text::{AnchorRangeExt, BufferId, ToPoint};
 use util::ResultExt;
@@ -50,6 +50,7 @@ pub(super) fn refresh_linked_ranges(
     cx: &mut Context<Editor>,
 ) -> Option<()> {
     if editor.ignore_lsp_data() || editor.pending_rename.is_some() {
+        editor.clear_linked_editing_ranges(cx);
         return None;
     }
     let project = editor.project()?.downgrade();
@@ -88,6 +89,9 @@ pub(super) fn refresh_linked_ranges(
             .ok()?;
 
         if applicable_selections.is_empty() {
+            editor
+                .update(cx, |editor, cx| editor.clear_linked_editing_ranges(cx))
+                .ok();
             return None;
         }
 
@@ -145,15 +149,14 @@ pub(super) fn refresh_linked_ranges(
         editor
             .update(cx, |this, cx| {
                 this.linked_edit_ranges.0.clear();
-                if this.pending_rename.is_some() {
-                    return;
-                }
-                for (buffer_id, ranges) in highlights.into_iter().flatten() {
-                    this.linked_edit_ranges
-                        .0
-                        .entry(buffer_id)
-                        .or_default()
-                        .extend(ranges);
+                if this.pending_rename.is_none() {
+                    for (buffer_id, ranges) in highlights.into_iter().flatten() {
+                        this.linked_edit_ranges
+                            .0
+                            .entry(buffer_id)
+                            .or_default()
+                            .extend(ranges);
+                    }
                 }
                 for (buffer_id, values) in this.linked_edit_ranges.0.iter_mut() {
                     let Some(snapshot) = this
@@ -166,6 +169,7 @@ pub(super) fn refresh_linked_ranges(
                     };
                     values.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0, &snapshot));
                 }
+                this.highlight_linked_editing_ranges(cx);
 
                 cx.notify();
             })
@@ -175,3 +179,43 @@ pub(super) fn refresh_linked_ranges(
     }));
     None
 }
+
+impl Editor {
+    pub(super) fn clear_linked_editing_ranges(&mut self, cx: &mut Context<Self>) {
+        if !self.linked_edit_ranges.is_empty() {
+            self.linked_edit_ranges.clear();
+            self.highlight_linked_editing_ranges(cx);
+        }
+    }
+
+    pub(super) fn highlight_linked_editing_ranges(&mut self, cx: &mut Context<Self>) {
+        let multi_buffer = self.buffer.read(cx);
+        let mut highlighted_ranges = Vec::new();
+        for (buffer_id, linked_ranges) in &self.linked_edit_ranges.0 {
+            let Some(buffer) = multi_buffer.buffer(*buffer_id) else {
+                continue;
+            };
+            let snapshot = buffer.read(cx).snapshot();
+            for (excerpt_id, excerpt_range) in multi_buffer.excerpts_for_buffer(*buffer_id, cx) {
+                for (range, _) in linked_ranges {
+                    let start = range.start.max(&excerpt_range.context.start, &snapshot);
+                    let end = range.end.min(&excerpt_range.context.end, &snapshot);
+                    if start.cmp(end, &snapshot).is_gt() {
+                        continue;
+                    }
+                    highlighted_ranges.push(Anchor::range_in_buffer(excerpt_id, *start..*end));
+                }
+            }
+        }
+
+        if highlighted_ranges.is_empty() {
+            self.clear_background_highlights::<LinkedEditingRanges>(cx);
+        } else {
+            self.highlight_background::<LinkedEditingRanges>(
+                &highlighted_ranges,
+                |_, theme| theme.colors().editor_linked_edit_background,
+                cx,
+            );
+        }
+    }
+}
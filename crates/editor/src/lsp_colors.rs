@@ -1,12 +1,12 @@
-use std::{cmp, ops::Range};
+use std::{cmp, iter, ops::Range};
 
 use collections::HashMap;
 use futures::future::join_all;
-use gpui::{Hsla, Rgba, Task};
+use gpui::{Focusable as _, Hsla, Rgba, Task};
 use itertools::Itertools;
-use language::point_from_lsp;
-use multi_buffer::Anchor;
-use project::{DocumentColor, InlayId};
+use language::{BufferSnapshot, point_from_lsp};
+use multi_buffer::{Anchor, MultiBufferSnapshot};
+use project::{ColorPresentation, DocumentColor, InlayId};
 use settings::Settings as _;
 use text::{Bias, BufferId, OffsetRangeExt as _};
 use ui::{App, Context, Window};
@@ -14,7 +14,10 @@ use util::post_inc;
 
 use crate::{
     DisplayPoint, Editor, EditorSettings, EditorSnapshot, FETCH_COLORS_DEBOUNCE_TIMEOUT,
-    InlaySplice, RangeToAnchorExt, editor_settings::DocumentColorsRenderMode, inlays::Inlay,
+    InlaySplice, RangeToAnchorExt, ShowColorPresentations,
+    editor_settings::DocumentColorsRenderMode,
+    inlays::Inlay,
+    mouse_context_menu::{MenuPosition, MouseContextMenu},
 };
 
 #[derive(Debug)]
@@ -113,6 +116,25 @@ impl LspColorData {
         true
     }
 
+    fn color_at(
+        &self,
+        position: Anchor,
+        snapshot: &MultiBufferSnapshot,
+    ) -> Option<(BufferId, Range<Anchor>, DocumentColor)> {
+        self.buffer_colors
+            .iter()
+            .find_map(|(buffer_id, buffer_colors)| {
+                buffer_colors
+                    .colors
+                    .iter()
+                    .find(|(range, _, _)| {
+                        range.start.cmp(&position, snapshot).is_le()
+                            && range.end.cmp(&position, snapshot).is_ge()
+                    })
+                    .map(|(range, color, _)| (*buffer_id, range.clone(), color.clone()))
+            })
+    }
+
     pub fn editor_display_highlights(
         &self,
         snapshot: &EditorSnapshot,
@@ -143,6 +165,100 @@ impl LspColorData {
 }
 
 impl Editor {
+    pub fn show_color_presentations(
+        &mut self,
+        _: &ShowColorPresentations,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor = self.selections.newest_anchor().head();
+        let Some((buffer_id, color_range, color)) = self
+            .colors
+            .as_ref()
+            .and_then(|colors| colors.color_at(cursor, &multi_buffer_snapshot))
+        else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+
+        let buffer_snapshot = buffer.read(cx).snapshot();
+
+        let resolve_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            lsp_store.color_presentations(color, buffer, cx)
+        });
+        cx.spawn_in(window, async move |editor, cx| {
+            let color = resolve_task.await?;
+            editor.update_in(cx, |editor, window, cx| {
+                // The server computed the edits against the buffer as it was when the
+                // presentations were requested, so anchor them before the user edits further.
+                let presentations = color
+                    .color_presentations
+                    .iter()
+                    .map(|presentation| {
+                        let edits = color_presentation_edits(
+                            presentation,
+                            &color_range,
+                            &buffer_snapshot,
+                            &multi_buffer_snapshot,
+                        );
+                        (presentation.label.clone(), edits)
+                    })
+                    .collect::<Vec<_>>();
+                if presentations.is_empty() {
+                    return;
+                }
+                let weak_editor = cx.weak_entity();
+                let focus_handle = editor.focus_handle(cx);
+                let context_menu = ui::ContextMenu::build(window, cx, |menu, _, _| {
+                    presentations.into_iter().fold(
+                        menu.context(focus_handle),
+                        |menu, (label, edits)| {
+                            let weak_editor = weak_editor.clone();
+                            menu.entry(label, None, move |window, cx| {
+                                weak_editor
+                                    .update(cx, |editor, cx| {
+                                        editor.apply_color_presentation(edits.clone(), window, cx);
+                                    })
+                                    .ok();
+                            })
+                        },
+                    )
+                });
+                let character_size = editor.character_dimensions(window);
+                let menu_position = MenuPosition::PinnedToEditor {
+                    source: color_range.start,
+                    offset: gpui::point(character_size.em_width, character_size.line_height),
+                };
+                editor.mouse_context_menu = Some(MouseContextMenu::new(
+                    editor,
+                    menu_position,
+                    context_menu,
+                    window,
+                    cx,
+                ));
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn apply_color_presentation(
+        &mut self,
+        edits: Vec<(Range<Anchor>, String)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.transact(window, cx, |editor, _, cx| {
+            editor.edit(edits, cx);
+        });
+    }
+
     pub(super) fn refresh_colors_for_visible_range(
         &mut self,
         buffer_id: Option<BufferId>,
@@ -414,3 +530,34 @@ impl Editor {
         });
     }
 }
+
+fn color_presentation_edits(
+    presentation: &ColorPresentation,
+    color_range: &Range<Anchor>,
+    buffer_snapshot: &BufferSnapshot,
+    multi_buffer_snapshot: &MultiBufferSnapshot,
+) -> Vec<(Range<Anchor>, String)> {
+    let excerpt_id = color_range.start.excerpt_id;
+    let to_anchor_range = |range: lsp::Range| {
+        let start = buffer_snapshot.anchor_before(
+            buffer_snapshot.clip_point_utf16(point_from_lsp(range.start), Bias::Left),
+        );
+        let end = buffer_snapshot
+            .anchor_after(buffer_snapshot.clip_point_utf16(point_from_lsp(range.end), Bias::Left));
+        multi_buffer_snapshot.anchor_range_in_excerpt(excerpt_id, start..end)
+    };
+    // When a presentation has no edit, the spec says its label replaces the color range.
+    let primary_edit = match &presentation.text_edit {
+        Some(text_edit) => (to_anchor_range(text_edit.range), text_edit.new_text.clone()),
+        None => (Some(color_range.clone()), presentation.label.to_string()),
+    };
+    iter::once(primary_edit)
+        .chain(
+            presentation
+                .additional_text_edits
+                .iter()
+                .map(|text_edit| (to_anchor_range(text_edit.range), text_edit.new_text.clone())),
+        )
+        .filter_map(|(range, new_text)| Some((range?, new_text)))
+        .collect()
+}
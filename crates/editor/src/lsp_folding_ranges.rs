@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{Entity, Task};
+use itertools::Itertools as _;
+use language::{Buffer, language_settings::language_settings};
+use multi_buffer::Anchor;
+use settings::FoldingProvider;
+use text::BufferId;
+use ui::{App, Context, Window};
+
+use crate::{
+    Editor, FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT,
+    display_map::{Crease, CreaseId},
+};
+
+pub(super) struct LspFoldingRanges {
+    creases: HashMap<BufferId, Vec<CreaseId>>,
+    refresh_task: Task<()>,
+}
+
+impl LspFoldingRanges {
+    pub(super) fn new() -> Self {
+        Self {
+            creases: HashMap::default(),
+            refresh_task: Task::ready(()),
+        }
+    }
+}
+
+impl Editor {
+    pub(super) fn refresh_lsp_folding_ranges(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return;
+        }
+
+        self.lsp_folding_ranges.refresh_task = cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Ok(folding_range_tasks) = editor.update(cx, |editor, cx| {
+                let Some(project) = editor.project.clone() else {
+                    return Vec::new();
+                };
+                let (lsp_buffers, other_buffers): (Vec<_>, Vec<_>) = editor
+                    .visible_excerpts(true, cx)
+                    .into_values()
+                    .map(|(buffer, ..)| buffer)
+                    .filter(|editor_buffer| {
+                        let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                        for_buffer.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                            && editor.registered_buffers.contains_key(&editor_buffer_id)
+                    })
+                    .unique_by(|buffer| buffer.read(cx).remote_id())
+                    .partition(|buffer| prefers_lsp_folding_ranges(buffer, cx));
+
+                for buffer in other_buffers {
+                    editor.set_lsp_folding_ranges(buffer.read(cx).remote_id(), Vec::new(), cx);
+                }
+                lsp_buffers
+                    .into_iter()
+                    .map(|buffer| {
+                        let buffer_id = buffer.read(cx).remote_id();
+                        let folding_ranges_task =
+                            project.update(cx, |project, cx| project.folding_ranges(&buffer, cx));
+                        async move { (buffer_id, folding_ranges_task.await) }
+                    })
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+
+            let all_folding_ranges = join_all(folding_range_tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (buffer_id, folding_ranges) in all_folding_ranges {
+                        match folding_ranges {
+                            Ok(Some(folding_ranges)) => {
+                                editor.set_lsp_folding_ranges(buffer_id, folding_ranges, cx)
+                            }
+                            // The edit that outdated the ranges schedules another refresh.
+                            Ok(None) => {}
+                            Err(e) => log::error!("Failed to fetch folding ranges: {e:#}"),
+                        }
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Server-provided ranges are kept as creases, which take precedence over the indentation
+    /// based folds when querying what a row folds.
+    fn set_lsp_folding_ranges(
+        &mut self,
+        buffer_id: BufferId,
+        folding_ranges: Vec<Range<text::Anchor>>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(old_creases) = self.lsp_folding_ranges.creases.remove(&buffer_id) {
+            self.remove_creases(old_creases, cx);
+        }
+        if folding_ranges.is_empty() {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        let buffer_snapshot = buffer.read(cx).snapshot();
+
+        let placeholder = self.default_fold_placeholder(cx);
+        let excerpts = self.buffer.read(cx).excerpts_for_buffer(buffer_id, cx);
+        let mut creases = Vec::new();
+        for folding_range in folding_ranges {
+            for (excerpt_id, excerpt_range) in &excerpts {
+                if excerpt_range
+                    .context
+                    .start
+                    .cmp(&folding_range.start, &buffer_snapshot)
+                    .is_gt()
+                    || excerpt_range
+                        .context
+                        .end
+                        .cmp(&folding_range.end, &buffer_snapshot)
+                        .is_lt()
+                {
+                    continue;
+                }
+                creases.push(Crease::simple(
+                    Anchor::in_buffer(*excerpt_id, folding_range.start)
+                        ..Anchor::in_buffer(*excerpt_id, folding_range.end),
+                    placeholder.clone(),
+                ));
+            }
+        }
+
+        let crease_ids = self.insert_creases(creases, cx);
+        self.lsp_folding_ranges
+            .creases
+            .insert(buffer_id, crease_ids);
+    }
+}
+
+fn prefers_lsp_folding_ranges(buffer: &Entity<Buffer>, cx: &App) -> bool {
+    let buffer = buffer.read(cx);
+    let language_name = buffer.language().map(|language| language.name());
+    language_settings(language_name, buffer.file(), cx).folding_provider
+        == FoldingProvider::LanguageServer
+}
@@ -0,0 +1,118 @@
+//! Marks local variables that shadow another variable of the same name.
+//! Uses tree-sitter queries from locals.scm to find scopes and variable definitions.
+
+use gpui::{Context, HighlightStyle, UnderlineStyle, px};
+use language::language_settings;
+use ui::ActiveTheme;
+
+use crate::Editor;
+
+struct ShadowedVariablesHighlight;
+
+impl Editor {
+    pub(crate) fn highlight_shadowed_variables(&mut self, cx: &mut Context<Editor>) {
+        if !self.mode.is_full() {
+            return;
+        }
+
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let mut shadowing_ranges = self
+            .visible_excerpts(false, cx)
+            .into_iter()
+            .flat_map(|(excerpt_id, (buffer, _, buffer_range))| {
+                let buffer_snapshot = buffer.read(cx).snapshot();
+                let enabled = language_settings::language_settings(
+                    buffer_snapshot.language().map(|language| language.name()),
+                    buffer_snapshot.file(),
+                    cx,
+                )
+                .highlight_shadowed_variables;
+                if !enabled {
+                    return Vec::new();
+                }
+
+                buffer_snapshot
+                    .shadowing_definitions(buffer_range)
+                    .into_iter()
+                    .filter_map(|range| {
+                        multi_buffer_snapshot.anchor_range_in_excerpt(
+                            excerpt_id,
+                            buffer_snapshot.anchor_after(range.start)
+                                ..buffer_snapshot.anchor_before(range.end),
+                        )
+                    })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+
+        if shadowing_ranges.is_empty() {
+            self.clear_highlights::<ShadowedVariablesHighlight>(cx);
+            return;
+        }
+        shadowing_ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+
+        let syntax_theme = cx.theme().syntax();
+        let style = if syntax_theme.highlight_id("variable.shadowed").is_some() {
+            syntax_theme.get("variable.shadowed")
+        } else {
+            HighlightStyle {
+                underline: Some(UnderlineStyle {
+                    color: Some(cx.theme().status().warning),
+                    thickness: px(1.),
+                    wavy: true,
+                }),
+                ..HighlightStyle::default()
+            }
+        };
+        self.highlight_text::<ShadowedVariablesHighlight>(shadowing_ranges, style, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::*;
+    use crate::{editor_tests::init_test, test::editor_lsp_test_context::EditorLspTestContext};
+    use indoc::indoc;
+    use languages::rust_lang;
+
+    #[gpui::test]
+    async fn test_highlight_shadowed_variables(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |language_settings| {
+            language_settings.defaults.highlight_shadowed_variables = Some(true);
+        });
+        let mut cx = EditorLspTestContext::new(
+            Arc::into_inner(rust_lang()).unwrap(),
+            lsp::ServerCapabilities::default(),
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            fn main(count: usize) {
+                let count = count + 1;
+                let name = \"outer\";
+                {
+                    let name = \"inner\";
+                    let unrelated = name;
+                }
+                for unrelated in 0..countˇ {}
+            }
+        "});
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.executor().run_until_parked();
+
+        cx.assert_editor_text_highlights::<ShadowedVariablesHighlight>(indoc! {"
+            fn main(count: usize) {
+                let «count» = count + 1;
+                let name = \"outer\";
+                {
+                    let «name» = \"inner\";
+                    let unrelated = name;
+                }
+                for unrelated in 0..count {}
+            }
+        "});
+    }
+}
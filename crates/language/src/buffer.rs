@@ -868,6 +868,32 @@ impl EditPreview {
         highlighted_text.build()
     }
 
+    /// Returns the text inserted by the given edit, highlighted as it parses once all the
+    /// previewed edits are applied.
+    pub fn highlight_inserted_text(
+        &self,
+        range: &Range<Anchor>,
+        new_text: &str,
+        cx: &App,
+    ) -> HighlightedText {
+        let edit_new_end_in_preview_snapshot = range
+            .end
+            .bias_right(&self.old_snapshot)
+            .to_offset(&self.applied_edits_snapshot);
+        let edit_start_in_preview_snapshot =
+            edit_new_end_in_preview_snapshot.saturating_sub(new_text.len());
+
+        let mut highlighted_text = HighlightedTextBuilder::default();
+        highlighted_text.add_text_from_buffer_range(
+            edit_start_in_preview_snapshot..edit_new_end_in_preview_snapshot,
+            &self.applied_edits_snapshot,
+            &self.syntax_snapshot,
+            None,
+            cx.theme().syntax(),
+        );
+        highlighted_text.build()
+    }
+
     pub fn build_result_buffer(&self, cx: &mut App) -> Entity<Buffer> {
         cx.new(|cx| {
             let mut buffer = Buffer::local_normalized(
@@ -4722,6 +4748,80 @@ impl BufferSnapshot {
         })
     }
 
+    /// Returns the ranges of the local variable definitions intersecting the given range
+    /// that shadow an earlier definition of the same name, according to the language's
+    /// locals query.
+    pub fn shadowing_definitions<T: ToOffset>(&self, range: Range<T>) -> Vec<Range<usize>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+
+        // The shadowed definition may be outside of the range, so search every scope
+        // the range overlaps.
+        let mut search_range = range.clone();
+        let (scope_ranges, _) = self.locals_captures(range.clone());
+        for scope_range in scope_ranges {
+            search_range.start = search_range.start.min(scope_range.start);
+            search_range.end = search_range.end.max(scope_range.end);
+        }
+
+        let (scope_ranges, mut definition_ranges) = self.locals_captures(search_range);
+        definition_ranges.sort_by_key(|definition_range| definition_range.start);
+
+        let mut earlier_definitions = HashMap::<String, Vec<Range<usize>>>::default();
+        let mut shadowing_definitions = Vec::new();
+        for definition_range in definition_ranges {
+            let Some(definition_scope) = scope_ranges
+                .iter()
+                .filter(|scope_range| {
+                    scope_range.start <= definition_range.start
+                        && definition_range.end <= scope_range.end
+                })
+                .min_by_key(|scope_range| scope_range.len())
+            else {
+                continue;
+            };
+
+            let name = self
+                .text_for_range(definition_range.clone())
+                .collect::<String>();
+            let scopes_of_same_name = earlier_definitions.entry(name).or_default();
+            let shadows = scopes_of_same_name
+                .iter()
+                .any(|scope_range| scope_range.contains(&definition_range.start));
+            if shadows && definition_range.start < range.end && range.start < definition_range.end {
+                shadowing_definitions.push(definition_range);
+            }
+            scopes_of_same_name.push(definition_scope.clone());
+        }
+        shadowing_definitions
+    }
+
+    fn locals_captures(&self, range: Range<usize>) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+        let mut syntax_matches = self.syntax.matches(range, self, |grammar| {
+            grammar.locals_config.as_ref().map(|config| &config.query)
+        });
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.locals_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut scope_ranges = Vec::new();
+        let mut definition_ranges = Vec::new();
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                for capture in mat.captures {
+                    if capture.index == config.scope_capture_ix {
+                        scope_ranges.push(capture.node.byte_range());
+                    } else if capture.index == config.definition_capture_ix {
+                        definition_ranges.push(capture.node.byte_range());
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+        (scope_ranges, definition_ranges)
+    }
+
     pub fn injections_intersecting_range<T: ToOffset>(
         &self,
         range: Range<T>,
@@ -1301,6 +1301,67 @@ fn test_enclosing_bracket_ranges_where_brackets_are_not_outermost_children(cx: &
     );
 }
 
+#[gpui::test]
+fn test_shadowing_definitions(cx: &mut App) {
+    cx.new(|cx| {
+        let text = indoc! {r#"
+            fn main(count: usize) {
+                let count = count + 1;
+                let name = "outer";
+                {
+                    let name = "inner";
+                    let unrelated = 1;
+                }
+                for count in 0..count {}
+                let total = |count| count;
+                match Some(total) {
+                    Some(name) => {}
+                    None => {}
+                }
+            }
+
+            fn other() {
+                let name = 1;
+                let unrelated = 2;
+            }
+        "#};
+        let buffer = Buffer::local(text, cx).with_language(rust_lang(), cx);
+        let snapshot = buffer.snapshot();
+
+        let shadowing_definitions = snapshot
+            .shadowing_definitions(0..text.len())
+            .into_iter()
+            .map(|range| {
+                (
+                    &text[range.clone()],
+                    snapshot.offset_to_point(range.start).row,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shadowing_definitions,
+            [
+                ("count", 1),
+                ("name", 4),
+                ("count", 7),
+                ("count", 8),
+                ("name", 10)
+            ]
+        );
+
+        // Definitions shadowed from outside of the requested range are still found.
+        let inner_block = text.find("let name = \"inner\"").unwrap();
+        let shadowing_definitions = snapshot
+            .shadowing_definitions(inner_block..inner_block + "let name".len())
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(shadowing_definitions, ["name"]);
+
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_range_for_syntax_ancestor(cx: &mut App) {
     cx.new(|cx| {
@@ -3179,6 +3240,41 @@ async fn test_preview_edits(cx: &mut TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_preview_edits_highlight_inserted_text(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        init_settings(cx, |_| {});
+        theme::init(theme::LoadThemes::JustBase, cx);
+    });
+
+    let language = rust_lang();
+    cx.read(|cx| language.set_theme(cx.theme().syntax()));
+    let buffer = cx.new(|cx| Buffer::local("fn main() {}", cx).with_language(language, cx));
+    let edits = buffer.read_with(cx, |buffer, _| {
+        let position = Point::new(0, 11);
+        Arc::from([(
+            buffer.anchor_before(position)..buffer.anchor_after(position),
+            Arc::<str>::from(" let x = 1; "),
+        )])
+    });
+    let edit_preview = buffer
+        .read_with(cx, |buffer, cx| buffer.preview_edits(edits.clone(), cx))
+        .await;
+
+    let (range, new_text) = &edits[0];
+    let highlighted_text = cx.read(|cx| edit_preview.highlight_inserted_text(range, new_text, cx));
+    assert_eq!(highlighted_text.text, " let x = 1; ");
+
+    let keyword_style = cx.read(|cx| cx.theme().syntax().get("keyword"));
+    assert!(
+        highlighted_text
+            .highlights
+            .contains(&((1..4), keyword_style)),
+        "expected `let` to be highlighted as a keyword, got {:?}",
+        highlighted_text.highlights
+    );
+}
+
 #[gpui::test(iterations = 100)]
 fn test_random_collaboration(cx: &mut App, mut rng: StdRng) {
     let min_peers = env::var("MIN_PEERS")
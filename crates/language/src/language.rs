@@ -1275,6 +1275,7 @@ pub struct Grammar {
     pub(crate) override_config: Option<OverrideConfig>,
     pub(crate) debug_variables_config: Option<DebugVariablesConfig>,
     pub(crate) imports_config: Option<ImportsConfig>,
+    pub(crate) locals_config: Option<LocalsConfig>,
     pub(crate) highlight_map: Mutex<HighlightMap>,
 }
 
@@ -1428,6 +1429,12 @@ pub struct DebugVariablesConfig {
     pub objects_by_capture_ix: Vec<(u32, DebuggerTextObject)>,
 }
 
+struct LocalsConfig {
+    query: Query,
+    scope_capture_ix: u32,
+    definition_capture_ix: u32,
+}
+
 pub struct ImportsConfig {
     pub query: Query,
     pub import_ix: u32,
@@ -1472,6 +1479,7 @@ impl Language {
                     error_query: Query::new(&ts_language, "(ERROR) @error").ok(),
                     debug_variables_config: None,
                     imports_config: None,
+                    locals_config: None,
                     ts_language,
                     highlight_map: Default::default(),
                 })
@@ -1558,6 +1566,11 @@ impl Language {
                 .with_imports_query(query.as_ref())
                 .context("Error loading imports query")?;
         }
+        if let Some(query) = queries.locals {
+            self = self
+                .with_locals_query(query.as_ref())
+                .context("Error loading locals query")?;
+        }
         Ok(self)
     }
 
@@ -2010,6 +2023,29 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_locals_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let query = Query::new(&self.expect_grammar()?.ts_language, source)?;
+        let mut scope_capture_ix = 0;
+        let mut definition_capture_ix = 0;
+        if populate_capture_indices(
+            &query,
+            &self.config.name,
+            "locals",
+            &[],
+            &mut [
+                Capture::Required("scope", &mut scope_capture_ix),
+                Capture::Required("definition", &mut definition_capture_ix),
+            ],
+        ) {
+            self.grammar_mut()?.locals_config = Some(LocalsConfig {
+                query,
+                scope_capture_ix,
+                definition_capture_ix,
+            });
+        }
+        Ok(self)
+    }
+
     fn expect_grammar(&self) -> Result<&Grammar> {
         self.grammar
             .as_ref()
@@ -2753,6 +2789,9 @@ pub fn rust_lang() -> Arc<Language> {
         imports: Some(Cow::from(include_str!(
             "../../languages/src/rust/imports.scm"
         ))),
+        locals: Some(Cow::from(include_str!(
+            "../../languages/src/rust/locals.scm"
+        ))),
     })
     .expect("Could not parse queries");
     Arc::new(language)
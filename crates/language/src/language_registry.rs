@@ -236,6 +236,7 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("debugger", |q| &mut q.debugger),
     ("textobjects", |q| &mut q.text_objects),
     ("imports", |q| &mut q.imports),
+    ("locals", |q| &mut q.locals),
 ];
 
 /// Tree-sitter language queries for a given language.
@@ -253,6 +254,7 @@ pub struct LanguageQueries {
     pub text_objects: Option<Cow<'static, str>>,
     pub debugger: Option<Cow<'static, str>>,
     pub imports: Option<Cow<'static, str>>,
+    pub locals: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Default)]
@@ -11,8 +11,8 @@ use gpui::{App, Modifiers, SharedString};
 use itertools::{Either, Itertools};
 
 pub use settings::{
-    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
-    Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
+    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FoldingProvider,
+    FormatOnSave, Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
     RewrapBehavior, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
 };
 use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore};
@@ -162,6 +162,11 @@ pub struct LanguageSettings {
     pub word_diff_enabled: bool,
     /// Whether to use tree-sitter bracket queries to detect and colorize the brackets in the editor.
     pub colorize_brackets: bool,
+    /// Whether to use tree-sitter locals queries to mark local variables that shadow
+    /// another variable of the same name.
+    pub highlight_shadowed_variables: bool,
+    /// Which source of folding ranges to prefer for this language.
+    pub folding_provider: FoldingProvider,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -388,6 +393,8 @@ pub struct EditPredictionSettings {
     /// Whether edit predictions are enabled in the assistant panel.
     /// This setting has no effect if globally disabled.
     pub enabled_in_text_threads: bool,
+    /// Whether predicted text shown inline in the buffer is syntax highlighted.
+    pub syntax_highlighting: bool,
 }
 
 impl EditPredictionSettings {
@@ -596,6 +603,8 @@ impl settings::Settings for AllLanguageSettings {
                 show_completions_on_input: settings.show_completions_on_input.unwrap(),
                 show_completion_documentation: settings.show_completion_documentation.unwrap(),
                 colorize_brackets: settings.colorize_brackets.unwrap(),
+                highlight_shadowed_variables: settings.highlight_shadowed_variables.unwrap(),
+                folding_provider: settings.folding_provider.unwrap(),
                 completions: CompletionSettings {
                     words: completions.words.unwrap(),
                     words_min_length: completions.words_min_length.unwrap() as usize,
@@ -655,6 +664,7 @@ impl settings::Settings for AllLanguageSettings {
         };
 
         let enabled_in_text_threads = edit_predictions.enabled_in_text_threads.unwrap();
+        let syntax_highlighting = edit_predictions.syntax_highlighting.unwrap();
 
         let mut file_types: FxHashMap<Arc<str>, (GlobSet, Vec<String>)> = FxHashMap::default();
 
@@ -693,6 +703,7 @@ impl settings::Settings for AllLanguageSettings {
                 copilot: copilot_settings,
                 codestral: codestral_settings,
                 enabled_in_text_threads,
+                syntax_highlighting,
             },
             defaults: default_language_settings,
             languages,
@@ -4,12 +4,18 @@
   (pipe_table)
 ] @text
 
-[
-  (atx_heading)
-  (setext_heading)
-  (thematic_break)
-] @title.markup
-(setext_heading (paragraph) @title.markup)
+(thematic_break) @title.markup
+
+(atx_heading (atx_h1_marker)) @title.markup.h1
+(atx_heading (atx_h2_marker)) @title.markup.h2
+(atx_heading (atx_h3_marker)) @title.markup.h3
+(atx_heading (atx_h4_marker)) @title.markup.h4
+(atx_heading (atx_h5_marker)) @title.markup.h5
+(atx_heading (atx_h6_marker)) @title.markup.h6
+(setext_heading (setext_h1_underline)) @title.markup.h1
+(setext_heading (setext_h2_underline)) @title.markup.h2
+(setext_heading (paragraph) @title.markup.h1 (setext_h1_underline))
+(setext_heading (paragraph) @title.markup.h2 (setext_h2_underline))
 
 [
   (list_marker_plus)
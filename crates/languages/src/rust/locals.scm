@@ -0,0 +1,31 @@
+[
+  (block)
+  (function_item)
+  (closure_expression)
+  (for_expression)
+  (while_expression)
+  (if_expression)
+  (match_arm)
+] @scope
+
+(parameter pattern: (identifier) @definition)
+(closure_parameters (identifier) @definition)
+
+(let_declaration pattern: (identifier) @definition)
+(let_condition pattern: (identifier) @definition)
+(for_expression pattern: (identifier) @definition)
+(match_pattern . (identifier) @definition
+  (#not-match? @definition "^[A-Z]"))
+
+(tuple_pattern (identifier) @definition
+  (#not-match? @definition "^[A-Z]"))
+(tuple_struct_pattern (identifier) @definition
+  (#not-match? @definition "^[A-Z]"))
+(slice_pattern (identifier) @definition
+  (#not-match? @definition "^[A-Z]"))
+(field_pattern name: (shorthand_field_identifier) @definition)
+(field_pattern pattern: (identifier) @definition)
+(captured_pattern . (identifier) @definition)
+(ref_pattern (identifier) @definition)
+(mut_pattern (identifier) @definition)
+(reference_pattern (identifier) @definition)
@@ -873,6 +873,19 @@ impl LanguageServer {
                     color_provider: Some(DocumentColorClientCapabilities {
                         dynamic_registration: Some(true),
                     }),
+                    call_hierarchy: Some(CallHierarchyClientCapabilities::default()),
+                    type_hierarchy: Some(TypeHierarchyClientCapabilities::default()),
+                    inline_value: Some(InlineValueClientCapabilities::default()),
+                    selection_range: Some(SelectionRangeClientCapabilities::default()),
+                    document_link: Some(DocumentLinkClientCapabilities {
+                        dynamic_registration: Some(true),
+                        tooltip_support: Some(true),
+                    }),
+                    folding_range: Some(FoldingRangeClientCapabilities {
+                        dynamic_registration: Some(true),
+                        line_folding_only: Some(true),
+                        ..FoldingRangeClientCapabilities::default()
+                    }),
                     ..TextDocumentClientCapabilities::default()
                 }),
                 experimental: Some(json!({
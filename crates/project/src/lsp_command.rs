@@ -1,12 +1,13 @@
+mod hierarchy;
 mod signature_help;
 
 use crate::{
     CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
+    DocumentHighlight, DocumentLink, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
     InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
     LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
     ProjectTransaction, PulledDiagnostics, ResolveState,
-    lsp_store::{LocalLspStore, LspStore},
+    lsp_store::{LanguageServerToQuery, LocalLspStore, LspStore},
 };
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
@@ -17,7 +18,7 @@ use futures::future;
 use gpui::{App, AsyncApp, Entity, SharedString, Task};
 use language::{
     Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, CharScopeContext,
-    OffsetRangeExt, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    OffsetRangeExt, Point, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
     proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
@@ -26,8 +27,9 @@ use language::{
 use lsp::{
     AdapterServerCapabilities, CodeActionKind, CodeActionOptions, CodeDescription,
     CompletionContext, CompletionListItemDefaultsEditRange, CompletionTriggerKind,
-    DocumentHighlightKind, LanguageServer, LanguageServerId, LinkedEditingRangeServerCapabilities,
-    OneOf, RenameOptions, ServerCapabilities,
+    DocumentHighlightKind, FoldingRangeProviderCapability, LanguageServer, LanguageServerId,
+    LinkedEditingRangeServerCapabilities, OneOf, RenameOptions, SelectionRangeProviderCapability,
+    ServerCapabilities,
 };
 use serde_json::Value;
 use signature_help::{lsp_to_proto_signature, proto_to_lsp_signature};
@@ -37,6 +39,10 @@ use std::{
 use text::{BufferId, LineEnding};
 use util::{ResultExt as _, debug_panic};
 
+pub(crate) use hierarchy::{
+    CallHierarchy, Hierarchy, HierarchyQuery, IncomingCalls, OutgoingCalls, PrepareHierarchy,
+    QueryHierarchy, Subtypes, Supertypes, TypeHierarchy,
+};
 pub use signature_help::SignatureHelp;
 
 pub fn lsp_formatting_options(settings: &LanguageSettings) -> lsp::FormattingOptions {
@@ -109,6 +115,11 @@ pub trait LspCommand: 'static + Sized + Send + std::fmt::Debug {
     /// When false, `to_lsp_params_or_response` default implementation will return the default response.
     fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool;
 
+    /// The language server to query when the request comes from a peer.
+    fn server_to_query(&self) -> LanguageServerToQuery {
+        LanguageServerToQuery::FirstCapable
+    }
+
     fn to_lsp(
         &self,
         path: &Path,
@@ -233,6 +244,8 @@ pub(crate) struct OnTypeFormatting {
     pub trigger: String,
     pub options: lsp::FormattingOptions,
     pub push_to_history: bool,
+    /// The buffer version the formatting was requested for.
+    pub version: clock::Global,
 }
 
 #[derive(Clone, Debug)]
@@ -246,6 +259,39 @@ pub(crate) struct GetCodeLens;
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentColor;
 
+#[derive(Debug, Clone)]
+pub(crate) struct GetInlineValues {
+    pub range: Range<Anchor>,
+    pub stopped_location: Range<Anchor>,
+    pub frame_id: i32,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetFoldingRanges {
+    /// The buffer version the folding ranges were requested for.
+    pub version: clock::Global,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<Anchor>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentLinks;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResolveDocumentLink {
+    pub lsp_link: lsp::DocumentLink,
+    pub server_id: LanguageServerId,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResolveCodeLens {
+    pub lsp_lens: lsp::CodeLens,
+    pub server_id: LanguageServerId,
+}
+
 impl GetCodeLens {
     pub(crate) fn can_resolve_lens(capabilities: &ServerCapabilities) -> bool {
         capabilities
@@ -1449,6 +1495,94 @@ impl LspCommand for GetReferences {
     }
 }
 
+async fn locations_from_lsp(
+    lsp_locations: impl IntoIterator<Item = (lsp::Uri, lsp::Range)>,
+    lsp_store: &Entity<LspStore>,
+    buffer: &Entity<Buffer>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<Vec<Location>> {
+    let (_, language_server) = language_server_for_buffer(lsp_store, buffer, server_id, cx)?;
+    let mut locations = Vec::new();
+    for (uri, range) in lsp_locations {
+        let target_buffer_handle = lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.open_local_buffer_via_lsp(uri, language_server.server_id(), cx)
+            })?
+            .await?;
+        target_buffer_handle
+            .clone()
+            .read_with(cx, |target_buffer, _| {
+                let target_start =
+                    target_buffer.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+                let target_end =
+                    target_buffer.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+                locations.push(Location {
+                    buffer: target_buffer_handle,
+                    range: target_buffer.anchor_after(target_start)
+                        ..target_buffer.anchor_before(target_end),
+                });
+            })?;
+    }
+    Ok(locations)
+}
+
+fn locations_to_proto(
+    locations: Vec<Location>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> Vec<proto::Location> {
+    locations
+        .into_iter()
+        .map(|location| {
+            lsp_store
+                .buffer_store()
+                .update(cx, |buffer_store, cx| {
+                    buffer_store.create_buffer_for_peer(&location.buffer, peer_id, cx)
+                })
+                .detach_and_log_err(cx);
+            proto::Location {
+                start: Some(serialize_anchor(&location.range.start)),
+                end: Some(serialize_anchor(&location.range.end)),
+                buffer_id: location.buffer.read(cx).remote_id().into(),
+            }
+        })
+        .collect()
+}
+
+async fn locations_from_proto(
+    proto_locations: Vec<proto::Location>,
+    lsp_store: Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<Vec<Location>> {
+    let mut locations = Vec::new();
+    for location in proto_locations {
+        let buffer_id = BufferId::new(location.buffer_id)?;
+        let target_buffer = lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.wait_for_remote_buffer(buffer_id, cx)
+            })?
+            .await?;
+        let start = location
+            .start
+            .and_then(deserialize_anchor)
+            .context("missing target start")?;
+        let end = location
+            .end
+            .and_then(deserialize_anchor)
+            .context("missing target end")?;
+        target_buffer
+            .update(cx, |buffer, _| buffer.wait_for_anchors([start, end]))?
+            .await?;
+        locations.push(Location {
+            buffer: target_buffer,
+            range: start..end,
+        })
+    }
+    Ok(locations)
+}
+
 #[async_trait(?Send)]
 impl LspCommand for GetDocumentHighlights {
     type Response = Vec<DocumentHighlight>;
@@ -2832,6 +2966,14 @@ impl LspCommand for OnTypeFormatting {
         mut cx: AsyncApp,
     ) -> Result<Option<Transaction>> {
         if let Some(edits) = message {
+            // The server formatted the text as it was when the trigger was typed: applying its
+            // edits on top of the input that arrived since could mangle it.
+            let edited_since_request = buffer.read_with(&cx, |buffer, _| {
+                buffer.version().changed_since(&self.version)
+            })?;
+            if edited_since_request {
+                return Ok(None);
+            }
             let (lsp_adapter, lsp_server) =
                 language_server_for_buffer(&lsp_store, &buffer, server_id, &mut cx)?;
             LocalLspStore::deserialize_text_edits(
@@ -2883,11 +3025,15 @@ impl LspCommand for OnTypeFormatting {
             )
         })?;
 
+        let (position, version) = buffer.read_with(&cx, |buffer, _| {
+            (position.to_point_utf16(buffer), buffer.version())
+        })?;
         Ok(Self {
-            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+            position,
             trigger: message.trigger.clone(),
             options,
             push_to_history: false,
+            version,
         })
     }
 
@@ -3609,6 +3755,777 @@ impl LinkedEditingRange {
     }
 }
 
+#[async_trait(?Send)]
+impl LspCommand for GetInlineValues {
+    type Response = Vec<lsp::InlineValue>;
+    type LspRequest = lsp::request::InlineValueRequest;
+    type ProtoRequest = proto::GetInlineValues;
+
+    fn display_name(&self) -> &str {
+        "Inline values"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.inline_value_provider {
+            Some(OneOf::Left(has_support)) => *has_support,
+            Some(OneOf::Right(_)) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::InlineValueParams> {
+        Ok(lsp::InlineValueParams {
+            work_done_progress_params: Default::default(),
+            text_document: make_text_document_identifier(path)?,
+            range: range_to_lsp(self.range.to_point_utf16(buffer))?,
+            context: lsp::InlineValueContext {
+                frame_id: self.frame_id,
+                stopped_location: range_to_lsp(self.stopped_location.to_point_utf16(buffer))?,
+            },
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        inline_values: Option<Vec<lsp::InlineValue>>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Vec<lsp::InlineValue>> {
+        Ok(inline_values.unwrap_or_default())
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetInlineValues {
+        proto::GetInlineValues {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            start: Some(serialize_anchor(&self.range.start)),
+            end: Some(serialize_anchor(&self.range.end)),
+            stopped_start: Some(serialize_anchor(&self.stopped_location.start)),
+            stopped_end: Some(serialize_anchor(&self.stopped_location.end)),
+            frame_id: self.frame_id,
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetInlineValues,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let start = message
+            .start
+            .and_then(deserialize_anchor)
+            .context("invalid start")?;
+        let end = message
+            .end
+            .and_then(deserialize_anchor)
+            .context("invalid end")?;
+        let stopped_start = message
+            .stopped_start
+            .and_then(deserialize_anchor)
+            .context("invalid stopped location start")?;
+        let stopped_end = message
+            .stopped_end
+            .and_then(deserialize_anchor)
+            .context("invalid stopped location end")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            range: start..end,
+            stopped_location: stopped_start..stopped_end,
+            frame_id: message.frame_id,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<lsp::InlineValue>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetInlineValuesResponse {
+        proto::GetInlineValuesResponse {
+            values: response
+                .iter()
+                .filter_map(|value| serde_json::to_vec(value).log_err())
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetInlineValuesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<lsp::InlineValue>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .values
+            .iter()
+            .map(|value| serde_json::from_slice(value).context("deserializing inline value"))
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetInlineValues) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Option<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Folding ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.folding_range_provider {
+            Some(FoldingRangeProviderCapability::Simple(has_support)) => *has_support,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        folding_ranges: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Option<Vec<Range<Anchor>>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            // The lines are relative to the text the server was asked about, and converting them
+            // against a newer buffer would fold the wrong rows.
+            if buffer.version().changed_since(&self.version) {
+                return None;
+            }
+            let max_row = buffer.max_point().row;
+            let folding_ranges = folding_ranges
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|folding_range| {
+                    folding_range.start_line < folding_range.end_line
+                        && folding_range.end_line <= max_row
+                })
+                .map(|folding_range| {
+                    // Line folding only was requested, so fold from the end of the start line,
+                    // keeping the line the range starts on visible.
+                    let start = Point::new(
+                        folding_range.start_line,
+                        buffer.line_len(folding_range.start_line),
+                    );
+                    let end = Point::new(
+                        folding_range.end_line,
+                        buffer.line_len(folding_range.end_line),
+                    );
+                    buffer.anchor_after(start)..buffer.anchor_before(end)
+                })
+                .collect();
+            Some(folding_ranges)
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&self.version),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            version: buffer.read_with(&cx, |buffer, _| buffer.version())?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            outdated: response.is_none(),
+            ranges: response
+                .unwrap_or_default()
+                .into_iter()
+                .map(|range| proto::AnchorRange {
+                    start: Some(serialize_anchor(&range.start)),
+                    end: Some(serialize_anchor(&range.end)),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Option<Vec<Range<Anchor>>>> {
+        if message.outdated {
+            return Ok(None);
+        }
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Some(
+            message
+                .ranges
+                .into_iter()
+                .filter_map(|range| {
+                    let start = deserialize_anchor(range.start?)?;
+                    let end = deserialize_anchor(range.end?)?;
+                    Some(start..end)
+                })
+                .collect(),
+        ))
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    /// For every requested position, the ranges containing it from the innermost to the outermost.
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Selection ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.selection_range_provider {
+            Some(SelectionRangeProviderCapability::Simple(has_support)) => *has_support,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self
+                .positions
+                .iter()
+                .map(|position| point_to_lsp(position.to_point_utf16(buffer)))
+                .collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        selection_ranges: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            selection_ranges
+                .unwrap_or_default()
+                .into_iter()
+                .map(|selection_range| {
+                    let mut chain = Vec::new();
+                    let mut next = Some(selection_range);
+                    while let Some(selection_range) = next {
+                        let start = buffer.clip_point_utf16(
+                            point_from_lsp(selection_range.range.start),
+                            Bias::Left,
+                        );
+                        let end = buffer.clip_point_utf16(
+                            point_from_lsp(selection_range.range.end),
+                            Bias::Left,
+                        );
+                        chain.push(buffer.anchor_before(start)..buffer.anchor_after(end));
+                        next = selection_range.parent.map(|parent| *parent);
+                    }
+                    chain
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            positions: self.positions.iter().map(serialize_anchor).collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(positions.iter().copied())
+            })?
+            .await?;
+        Ok(Self { positions })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            chains: response
+                .into_iter()
+                .map(|chain| proto::SelectionRangeChain {
+                    ranges: chain
+                        .into_iter()
+                        .map(|range| proto::AnchorRange {
+                            start: Some(serialize_anchor(&range.start)),
+                            end: Some(serialize_anchor(&range.end)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(message
+            .chains
+            .into_iter()
+            .map(|chain| {
+                chain
+                    .ranges
+                    .into_iter()
+                    .filter_map(|range| {
+                        let start = deserialize_anchor(range.start?)?;
+                        let end = deserialize_anchor(range.end?)?;
+                        Some(start..end)
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Document links"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        links: Option<Vec<lsp::DocumentLink>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer.read_with(&cx, |buffer, _| {
+            links
+                .unwrap_or_default()
+                .into_iter()
+                .map(|lsp_link| {
+                    let start =
+                        buffer.clip_point_utf16(point_from_lsp(lsp_link.range.start), Bias::Left);
+                    let end =
+                        buffer.clip_point_utf16(point_from_lsp(lsp_link.range.end), Bias::Left);
+                    DocumentLink {
+                        range: buffer.anchor_after(start)..buffer.anchor_before(end),
+                        lsp_link,
+                        server_id,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentLinks {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentLinks,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response
+                .into_iter()
+                .filter_map(|link| {
+                    Some(proto::DocumentLink {
+                        range: Some(proto::AnchorRange {
+                            start: Some(serialize_anchor(&link.range.start)),
+                            end: Some(serialize_anchor(&link.range.end)),
+                        }),
+                        lsp_link: serde_json::to_vec(&link.lsp_link).log_err()?,
+                        server_id: link.server_id.to_proto(),
+                    })
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(message
+            .links
+            .into_iter()
+            .filter_map(|link| {
+                let range = link.range?;
+                Some(DocumentLink {
+                    range: deserialize_anchor(range.start?)?..deserialize_anchor(range.end?)?,
+                    lsp_link: serde_json::from_slice(&link.lsp_link).log_err()?,
+                    server_id: LanguageServerId::from_proto(link.server_id),
+                })
+            })
+            .collect())
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentLinks) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ResolveDocumentLink {
+    type Response = Option<lsp::DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkResolve;
+    type ProtoRequest = proto::ResolveDocumentLink;
+
+    fn display_name(&self) -> &str {
+        "Resolve document link"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .as_ref()
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false)
+    }
+
+    fn server_to_query(&self) -> LanguageServerToQuery {
+        LanguageServerToQuery::Other(self.server_id)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLink> {
+        Ok(self.lsp_link.clone())
+    }
+
+    async fn response_from_lsp(
+        self,
+        lsp_link: lsp::DocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::DocumentLink>> {
+        Ok(Some(lsp_link))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::ResolveDocumentLink {
+        proto::ResolveDocumentLink {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            lsp_link: serde_json::to_vec(&self.lsp_link).unwrap_or_default(),
+            server_id: self.server_id.to_proto(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::ResolveDocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            lsp_link: serde_json::from_slice(&message.lsp_link)
+                .context("deserializing document link")?,
+            server_id: LanguageServerId::from_proto(message.server_id),
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<lsp::DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::ResolveDocumentLinkResponse {
+        proto::ResolveDocumentLinkResponse {
+            lsp_link: response.and_then(|lsp_link| serde_json::to_vec(&lsp_link).log_err()),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::ResolveDocumentLinkResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::DocumentLink>> {
+        message
+            .lsp_link
+            .map(|lsp_link| {
+                serde_json::from_slice(&lsp_link).context("deserializing document link")
+            })
+            .transpose()
+    }
+
+    fn buffer_id_from_proto(message: &proto::ResolveDocumentLink) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ResolveCodeLens {
+    type Response = Option<lsp::CodeLens>;
+    type LspRequest = lsp::request::CodeLensResolve;
+    type ProtoRequest = proto::ResolveCodeLens;
+
+    fn display_name(&self) -> &str {
+        "Resolve code lens"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        GetCodeLens::can_resolve_lens(&capabilities.server_capabilities)
+    }
+
+    fn server_to_query(&self) -> LanguageServerToQuery {
+        LanguageServerToQuery::Other(self.server_id)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CodeLens> {
+        Ok(self.lsp_lens.clone())
+    }
+
+    async fn response_from_lsp(
+        self,
+        lsp_lens: lsp::CodeLens,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::CodeLens>> {
+        Ok(Some(lsp_lens))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::ResolveCodeLens {
+        proto::ResolveCodeLens {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            server_id: self.server_id.to_proto(),
+            lsp_lens: serde_json::to_vec(&self.lsp_lens).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::ResolveCodeLens,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            lsp_lens: serde_json::from_slice(&message.lsp_lens)
+                .context("deserializing code lens")?,
+            server_id: LanguageServerId::from_proto(message.server_id),
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<lsp::CodeLens>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::ResolveCodeLensResponse {
+        proto::ResolveCodeLensResponse {
+            lsp_lens: response.and_then(|lsp_lens| serde_json::to_vec(&lsp_lens).log_err()),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::ResolveCodeLensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::CodeLens>> {
+        message
+            .lsp_lens
+            .map(|lsp_lens| serde_json::from_slice(&lsp_lens).context("deserializing code lens"))
+            .transpose()
+    }
+
+    fn buffer_id_from_proto(message: &proto::ResolveCodeLens) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 #[async_trait(?Send)]
 impl LspCommand for LinkedEditingRange {
     type Response = Vec<Range<Anchor>>;
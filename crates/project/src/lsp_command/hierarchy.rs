@@ -0,0 +1,479 @@
+use std::{fmt, marker::PhantomData, path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use client::proto::{self, PeerId};
+use gpui::{App, AsyncApp, Entity};
+use language::{
+    Buffer, PointUtf16, ToPointUtf16 as _,
+    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+};
+use lsp::{AdapterServerCapabilities, LanguageServer, LanguageServerId, ServerCapabilities};
+use serde::{Serialize, de::DeserializeOwned};
+use text::BufferId;
+use util::ResultExt as _;
+
+use super::{
+    LspCommand, locations_from_lsp, locations_from_proto, locations_to_proto,
+    make_lsp_text_document_position,
+};
+use crate::{
+    Location,
+    lsp_store::{LanguageServerToQuery, LspStore},
+};
+
+/// A hierarchy that a language server builds around the symbol at a position: the symbol is
+/// prepared into hierarchy items first, which are then queried for their related symbols.
+pub(crate) trait Hierarchy: 'static + fmt::Debug + Send {
+    type Item: 'static + Clone + fmt::Debug + Send + Serialize + DeserializeOwned;
+    type PrepareRequest: 'static + Send + lsp::request::Request<Result = Option<Vec<Self::Item>>>;
+
+    const KIND: proto::HierarchyKind;
+    const PREPARE_NAME: &'static str;
+
+    fn is_supported(capabilities: &ServerCapabilities) -> bool;
+
+    fn prepare_params(
+        position: lsp::TextDocumentPositionParams,
+    ) -> <Self::PrepareRequest as lsp::request::Request>::Params;
+}
+
+/// A query for the symbols related to a prepared hierarchy item.
+pub(crate) trait HierarchyQuery: 'static + fmt::Debug + Send {
+    type Hierarchy: Hierarchy;
+    type LspRequest: 'static + Send + lsp::request::Request;
+
+    const QUERY: proto::HierarchyQuery;
+    const NAME: &'static str;
+    const STATUS: &'static str;
+
+    fn params(item: HierarchyItem<Self>) -> <Self::LspRequest as lsp::request::Request>::Params;
+
+    /// The locations to navigate to for the related symbols in the server's response.
+    fn targets(
+        response: <Self::LspRequest as lsp::request::Request>::Result,
+    ) -> Vec<(lsp::Uri, lsp::Range)>;
+}
+
+type HierarchyItem<Q> = <<Q as HierarchyQuery>::Hierarchy as Hierarchy>::Item;
+
+#[derive(Debug)]
+pub(crate) struct PrepareHierarchy<H> {
+    pub position: PointUtf16,
+    hierarchy: PhantomData<H>,
+}
+
+impl<H> PrepareHierarchy<H> {
+    pub fn new(position: PointUtf16) -> Self {
+        Self {
+            position,
+            hierarchy: PhantomData,
+        }
+    }
+}
+
+/// Items can only be queried from the language server that prepared them.
+#[derive(Debug)]
+pub(crate) struct PreparedHierarchy<Item> {
+    pub server_id: Option<LanguageServerId>,
+    pub items: Vec<Item>,
+}
+
+impl<Item> Default for PreparedHierarchy<Item> {
+    fn default() -> Self {
+        Self {
+            server_id: None,
+            items: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct QueryHierarchy<Q: HierarchyQuery> {
+    pub item: HierarchyItem<Q>,
+    pub server_id: LanguageServerId,
+}
+
+#[async_trait(?Send)]
+impl<H: Hierarchy> LspCommand for PrepareHierarchy<H> {
+    type Response = PreparedHierarchy<H::Item>;
+    type LspRequest = H::PrepareRequest;
+    type ProtoRequest = proto::PrepareHierarchy;
+
+    fn display_name(&self) -> &str {
+        H::PREPARE_NAME
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        H::is_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<<H::PrepareRequest as lsp::request::Request>::Params> {
+        Ok(H::prepare_params(make_lsp_text_document_position(
+            path,
+            self.position,
+        )?))
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<H::Item>>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<PreparedHierarchy<H::Item>> {
+        Ok(PreparedHierarchy {
+            server_id: Some(server_id),
+            items: items.unwrap_or_default(),
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareHierarchy {
+        proto::PrepareHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+            kind: H::KIND as i32,
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let position = buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?;
+        Ok(Self::new(position))
+    }
+
+    fn response_to_proto(
+        response: PreparedHierarchy<H::Item>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::PrepareHierarchyResponse {
+        proto::PrepareHierarchyResponse {
+            server_id: response.server_id.map(|server_id| server_id.to_proto()),
+            items: response
+                .items
+                .iter()
+                .filter_map(|item| serde_json::to_vec(item).log_err())
+                .collect(),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareHierarchyResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<PreparedHierarchy<H::Item>> {
+        Ok(PreparedHierarchy {
+            server_id: message.server_id.map(LanguageServerId::from_proto),
+            items: message
+                .items
+                .iter()
+                .map(|item| serde_json::from_slice(item).context("deserializing hierarchy item"))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Q: HierarchyQuery> LspCommand for QueryHierarchy<Q> {
+    type Response = Vec<Location>;
+    type LspRequest = Q::LspRequest;
+    type ProtoRequest = proto::GetHierarchyLocations;
+
+    fn display_name(&self) -> &str {
+        Q::NAME
+    }
+
+    fn status(&self) -> Option<String> {
+        Some(Q::STATUS.to_owned())
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        Q::Hierarchy::is_supported(&capabilities.server_capabilities)
+    }
+
+    fn server_to_query(&self) -> LanguageServerToQuery {
+        LanguageServerToQuery::Other(self.server_id)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<<Q::LspRequest as lsp::request::Request>::Params> {
+        Ok(Q::params(self.item.clone()))
+    }
+
+    async fn response_from_lsp(
+        self,
+        response: <Q::LspRequest as lsp::request::Request>::Result,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Location>> {
+        locations_from_lsp(
+            Q::targets(response),
+            &lsp_store,
+            &buffer,
+            server_id,
+            &mut cx,
+        )
+        .await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetHierarchyLocations {
+        proto::GetHierarchyLocations {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            query: Q::QUERY as i32,
+            server_id: self.server_id.to_proto(),
+            item: serde_json::to_vec(&self.item).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetHierarchyLocations,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: serde_json::from_slice(&message.item).context("deserializing hierarchy item")?,
+            server_id: LanguageServerId::from_proto(message.server_id),
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<Location>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyLocationsResponse {
+        proto::GetHierarchyLocationsResponse {
+            locations: locations_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyLocationsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Location>> {
+        locations_from_proto(message.locations, lsp_store, &mut cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetHierarchyLocations) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CallHierarchy;
+
+impl Hierarchy for CallHierarchy {
+    type Item = lsp::CallHierarchyItem;
+    type PrepareRequest = lsp::request::CallHierarchyPrepare;
+
+    const KIND: proto::HierarchyKind = proto::HierarchyKind::Call;
+    const PREPARE_NAME: &'static str = "Prepare call hierarchy";
+
+    fn is_supported(capabilities: &ServerCapabilities) -> bool {
+        match &capabilities.call_hierarchy_provider {
+            Some(lsp::CallHierarchyServerCapability::Simple(has_support)) => *has_support,
+            Some(lsp::CallHierarchyServerCapability::Options(_)) => true,
+            None => false,
+        }
+    }
+
+    fn prepare_params(
+        position: lsp::TextDocumentPositionParams,
+    ) -> lsp::CallHierarchyPrepareParams {
+        lsp::CallHierarchyPrepareParams {
+            text_document_position_params: position,
+            work_done_progress_params: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct IncomingCalls;
+
+impl HierarchyQuery for IncomingCalls {
+    type Hierarchy = CallHierarchy;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+
+    const QUERY: proto::HierarchyQuery = proto::HierarchyQuery::IncomingCalls;
+    const NAME: &'static str = "Find incoming calls";
+    const STATUS: &'static str = "Finding incoming calls...";
+
+    fn params(item: lsp::CallHierarchyItem) -> lsp::CallHierarchyIncomingCallsParams {
+        lsp::CallHierarchyIncomingCallsParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    // Point at the call sites within each caller, as that is what one navigates to.
+    fn targets(calls: Option<Vec<lsp::CallHierarchyIncomingCall>>) -> Vec<(lsp::Uri, lsp::Range)> {
+        calls
+            .into_iter()
+            .flatten()
+            .flat_map(|call| {
+                let caller = call.from;
+                let from_ranges = if call.from_ranges.is_empty() {
+                    vec![caller.selection_range]
+                } else {
+                    call.from_ranges
+                };
+                from_ranges
+                    .into_iter()
+                    .map(move |range| (caller.uri.clone(), range))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OutgoingCalls;
+
+impl HierarchyQuery for OutgoingCalls {
+    type Hierarchy = CallHierarchy;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+
+    const QUERY: proto::HierarchyQuery = proto::HierarchyQuery::OutgoingCalls;
+    const NAME: &'static str = "Find outgoing calls";
+    const STATUS: &'static str = "Finding outgoing calls...";
+
+    fn params(item: lsp::CallHierarchyItem) -> lsp::CallHierarchyOutgoingCallsParams {
+        lsp::CallHierarchyOutgoingCallsParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    // The call sites are all within the item itself, so point at the callees instead.
+    fn targets(calls: Option<Vec<lsp::CallHierarchyOutgoingCall>>) -> Vec<(lsp::Uri, lsp::Range)> {
+        calls
+            .into_iter()
+            .flatten()
+            .map(|call| (call.to.uri, call.to.selection_range))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TypeHierarchy;
+
+impl Hierarchy for TypeHierarchy {
+    type Item = lsp::TypeHierarchyItem;
+    type PrepareRequest = lsp::request::TypeHierarchyPrepare;
+
+    const KIND: proto::HierarchyKind = proto::HierarchyKind::Type;
+    const PREPARE_NAME: &'static str = "Prepare type hierarchy";
+
+    fn is_supported(capabilities: &ServerCapabilities) -> bool {
+        capabilities.type_hierarchy_provider.is_some()
+    }
+
+    fn prepare_params(
+        position: lsp::TextDocumentPositionParams,
+    ) -> lsp::TypeHierarchyPrepareParams {
+        lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: position,
+            work_done_progress_params: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Supertypes;
+
+impl HierarchyQuery for Supertypes {
+    type Hierarchy = TypeHierarchy;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+
+    const QUERY: proto::HierarchyQuery = proto::HierarchyQuery::Supertypes;
+    const NAME: &'static str = "Find supertypes";
+    const STATUS: &'static str = "Finding supertypes...";
+
+    fn params(item: lsp::TypeHierarchyItem) -> lsp::TypeHierarchySupertypesParams {
+        lsp::TypeHierarchySupertypesParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    fn targets(items: Option<Vec<lsp::TypeHierarchyItem>>) -> Vec<(lsp::Uri, lsp::Range)> {
+        type_targets(items)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Subtypes;
+
+impl HierarchyQuery for Subtypes {
+    type Hierarchy = TypeHierarchy;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+
+    const QUERY: proto::HierarchyQuery = proto::HierarchyQuery::Subtypes;
+    const NAME: &'static str = "Find subtypes";
+    const STATUS: &'static str = "Finding subtypes...";
+
+    fn params(item: lsp::TypeHierarchyItem) -> lsp::TypeHierarchySubtypesParams {
+        lsp::TypeHierarchySubtypesParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    fn targets(items: Option<Vec<lsp::TypeHierarchyItem>>) -> Vec<(lsp::Uri, lsp::Range)> {
+        type_targets(items)
+    }
+}
+
+fn type_targets(items: Option<Vec<lsp::TypeHierarchyItem>>) -> Vec<(lsp::Uri, lsp::Range)> {
+    items
+        .into_iter()
+        .flatten()
+        .map(|item| (item.uri, item.selection_range))
+        .collect()
+}
@@ -21,9 +21,9 @@ mod inlay_hint_cache;
 use self::inlay_hint_cache::BufferInlayHints;
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionDisplayOptions, CompletionResponse,
-    CompletionSource, CoreCompletion, DocumentColor, Hover, InlayHint, InlayId, LocationLink,
-    LspAction, LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem, ProjectPath,
-    ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
+    CompletionSource, CoreCompletion, DocumentColor, DocumentLink, Hover, InlayHint, InlayId,
+    LocationLink, LspAction, LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem,
+    ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -1012,6 +1012,7 @@ impl LocalLspStore {
                     let mut cx = cx.clone();
                     async move {
                         this.update(&mut cx, |this, cx| {
+                            this.invalidate_code_lens();
                             cx.emit(LspStoreEvent::RefreshCodeLens);
                             this.downstream_client.as_ref().map(|(client, project_id)| {
                                 client.send(proto::RefreshCodeLens {
@@ -3930,6 +3931,14 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(Self::handle_prepare_hierarchy);
+        client.add_entity_request_handler(Self::handle_get_hierarchy_locations);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetInlineValues>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<ResolveDocumentLink>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<ResolveCodeLens>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
         client.add_entity_request_handler(Self::handle_lsp_ext_run_flycheck);
@@ -5402,6 +5411,190 @@ impl LspStore {
         }
     }
 
+    /// Resolves the presentations of a color returned by [`Self::document_colors`],
+    /// using the language server that reported it.
+    pub fn color_presentations(
+        &mut self,
+        color: DocumentColor,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentColor>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        let server_id = self
+            .lsp_data
+            .get(&buffer_id)
+            .and_then(|lsp_data| lsp_data.document_colors.as_ref())
+            .and_then(|document_colors| {
+                document_colors
+                    .colors
+                    .iter()
+                    .find_map(|(server_id, colors)| colors.contains(&color).then_some(*server_id))
+            });
+        let Some(server_id) = server_id else {
+            return Task::ready(Err(anyhow!(
+                "no language server found for the color at {:?}",
+                color.lsp_range
+            )));
+        };
+        self.resolve_color_presentation(color, buffer, server_id, cx)
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        self.hierarchy_locations::<IncomingCalls>(buffer, position, cx)
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        self.hierarchy_locations::<OutgoingCalls>(buffer, position, cx)
+    }
+
+    /// Prepares the hierarchy items at the position and queries each of them for the related
+    /// symbols, on the language server that prepared the items.
+    fn hierarchy_locations<Q>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>>
+    where
+        Q: HierarchyQuery,
+        <<Q::Hierarchy as Hierarchy>::PrepareRequest as lsp::request::Request>::Params: Send,
+        <Q::LspRequest as lsp::request::Request>::Result: Send,
+        <Q::LspRequest as lsp::request::Request>::Params: Send,
+    {
+        let prepare_task = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareHierarchy::<Q::Hierarchy>::new(position),
+            cx,
+        );
+        let buffer = buffer.clone();
+        cx.spawn(async move |lsp_store, cx| {
+            let prepared = prepare_task.await?;
+            let Some(server_id) = prepared.server_id else {
+                return Ok(Vec::new());
+            };
+            let mut locations = Vec::new();
+            for item in prepared.items {
+                let locations_task = lsp_store.update(cx, |lsp_store, cx| {
+                    lsp_store.request_lsp(
+                        buffer.clone(),
+                        LanguageServerToQuery::Other(server_id),
+                        QueryHierarchy::<Q> { item, server_id },
+                        cx,
+                    )
+                })?;
+                locations.extend(locations_task.await?);
+            }
+            Ok(locations)
+        })
+    }
+
+    pub fn supertypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        self.hierarchy_locations::<Supertypes>(buffer, position, cx)
+    }
+
+    pub fn subtypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        self.hierarchy_locations::<Subtypes>(buffer, position, cx)
+    }
+
+    /// Returns `None` when the buffer changed before the server's ranges could be applied.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<Range<Anchor>>>>> {
+        let version = buffer.read(cx).version();
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetFoldingRanges { version },
+            cx,
+        )
+    }
+
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetDocumentLinks,
+            cx,
+        )
+    }
+
+    /// Fills in the target of a link that the language server reported without one.
+    pub fn resolve_document_link(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        if link.lsp_link.target.is_some() {
+            return Task::ready(Ok(link));
+        }
+        let resolve_task = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(link.server_id),
+            ResolveDocumentLink {
+                lsp_link: link.lsp_link.clone(),
+                server_id: link.server_id,
+            },
+            cx,
+        );
+        cx.background_spawn(async move {
+            Ok(match resolve_task.await? {
+                Some(lsp_link) => DocumentLink {
+                    range: link.range,
+                    lsp_link,
+                    server_id: link.server_id,
+                },
+                None => link,
+            })
+        })
+    }
+
+    /// Returns `None` when none of the buffer's language servers provides selection ranges.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Vec<Vec<Range<Anchor>>>>>> {
+        let request = GetSelectionRanges { positions };
+        if !self.is_capable_for_proto_request(buffer, &request, cx) {
+            return None;
+        }
+        Some(self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            request,
+            cx,
+        ))
+    }
+
     pub(crate) fn linked_edits(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -5557,6 +5750,7 @@ impl LspStore {
             {
                 waiter.await?;
             }
+            let version = buffer.read_with(cx, |buffer, _| buffer.version())?;
             cx.update(|cx| {
                 this.update(cx, |this, cx| {
                     this.request_lsp(
@@ -5567,6 +5761,7 @@ impl LspStore {
                             trigger,
                             options,
                             push_to_history,
+                            version,
                         },
                         cx,
                     )
@@ -6072,6 +6267,61 @@ impl LspStore {
         new_task
     }
 
+    /// Fills in the command of a lens that the language server reported without one, on the
+    /// server that reported it, and caches the result so that the lens is not resolved again.
+    pub fn resolve_code_lens(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        let LspAction::CodeLens(lsp_lens) = &action.lsp_action else {
+            return Task::ready(Ok(action));
+        };
+        if action.resolved || lsp_lens.command.is_some() {
+            return Task::ready(Ok(action));
+        }
+        let server_id = action.server_id;
+        let resolve_task = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(server_id),
+            ResolveCodeLens {
+                lsp_lens: lsp_lens.clone(),
+                server_id,
+            },
+            cx,
+        );
+        let buffer_id = buffer.read(cx).remote_id();
+        cx.spawn(async move |lsp_store, cx| {
+            let Some(lsp_lens) = resolve_task.await? else {
+                return Ok(action);
+            };
+            let resolved_action = CodeAction {
+                lsp_action: LspAction::CodeLens(lsp_lens),
+                resolved: true,
+                ..action.clone()
+            };
+            lsp_store.update(cx, |lsp_store, _| {
+                let cached_action = lsp_store
+                    .current_lsp_data(buffer_id)
+                    .and_then(|lsp_data| lsp_data.code_lens.as_mut())
+                    .and_then(|code_lens| code_lens.lens.get_mut(&server_id))
+                    .and_then(|lens| lens.iter_mut().find(|cached| **cached == action));
+                if let Some(cached_action) = cached_action {
+                    *cached_action = resolved_action.clone();
+                }
+            })?;
+            Ok(resolved_action)
+        })
+    }
+
+    /// Drops the cached lenses, so that the next query after `workspace/codeLens/refresh` refetches them.
+    fn invalidate_code_lens(&mut self) {
+        for lsp_data in self.lsp_data.values_mut() {
+            lsp_data.code_lens = None;
+        }
+    }
+
     fn fetch_code_lens(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -8865,14 +9115,10 @@ impl LspStore {
             cx.clone(),
         )
         .await?;
+        let server_to_query = request.server_to_query();
         let response = this
             .update(&mut cx, |this, cx| {
-                this.request_lsp(
-                    buffer_handle.clone(),
-                    LanguageServerToQuery::FirstCapable,
-                    request,
-                    cx,
-                )
+                this.request_lsp(buffer_handle.clone(), server_to_query, request, cx)
             })?
             .await?;
         this.update(&mut cx, |this, cx| {
@@ -8886,6 +9132,48 @@ impl LspStore {
         })?
     }
 
+    async fn handle_prepare_hierarchy(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::PrepareHierarchy>,
+        cx: AsyncApp,
+    ) -> Result<proto::PrepareHierarchyResponse> {
+        match proto::HierarchyKind::from_i32(envelope.payload.kind)
+            .context("invalid hierarchy kind")?
+        {
+            proto::HierarchyKind::Call => {
+                Self::handle_lsp_command::<PrepareHierarchy<CallHierarchy>>(this, envelope, cx)
+                    .await
+            }
+            proto::HierarchyKind::Type => {
+                Self::handle_lsp_command::<PrepareHierarchy<TypeHierarchy>>(this, envelope, cx)
+                    .await
+            }
+        }
+    }
+
+    async fn handle_get_hierarchy_locations(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetHierarchyLocations>,
+        cx: AsyncApp,
+    ) -> Result<proto::GetHierarchyLocationsResponse> {
+        match proto::HierarchyQuery::from_i32(envelope.payload.query)
+            .context("invalid hierarchy query")?
+        {
+            proto::HierarchyQuery::IncomingCalls => {
+                Self::handle_lsp_command::<QueryHierarchy<IncomingCalls>>(this, envelope, cx).await
+            }
+            proto::HierarchyQuery::OutgoingCalls => {
+                Self::handle_lsp_command::<QueryHierarchy<OutgoingCalls>>(this, envelope, cx).await
+            }
+            proto::HierarchyQuery::Supertypes => {
+                Self::handle_lsp_command::<QueryHierarchy<Supertypes>>(this, envelope, cx).await
+            }
+            proto::HierarchyQuery::Subtypes => {
+                Self::handle_lsp_command::<QueryHierarchy<Subtypes>>(this, envelope, cx).await
+            }
+        }
+    }
+
     async fn handle_lsp_query(
         lsp_store: Entity<Self>,
         envelope: TypedEnvelope<proto::LspQuery>,
@@ -10309,7 +10597,8 @@ impl LspStore {
         _: TypedEnvelope<proto::RefreshCodeLens>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
-        this.update(&mut cx, |_, cx| {
+        this.update(&mut cx, |this, cx| {
+            this.invalidate_code_lens();
             cx.emit(LspStoreEvent::RefreshCodeLens);
         })?;
         Ok(proto::Ack {})
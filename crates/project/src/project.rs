@@ -126,7 +126,7 @@ use std::{
 
 use task_store::TaskStore;
 use terminals::Terminals;
-use text::{Anchor, BufferId, OffsetRangeExt, Point, Rope};
+use text::{Anchor, Bias, BufferId, OffsetRangeExt, Point, Rope};
 use toolchain_store::EmptyToolchainStore;
 use util::{
     ResultExt as _, maybe,
@@ -157,6 +157,10 @@ pub use lsp_store::{
 };
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
+/// How many rows above the stopped line language servers are asked for inline values.
+/// Inline values are only shown near the execution position, and asking for the whole
+/// buffer prefix makes servers scan large files on every step.
+const INLINE_VALUE_ROWS_ABOVE_STOPPED_LINE: u32 = 100;
 
 pub trait ProjectItem: 'static {
     fn try_open(
@@ -861,6 +865,15 @@ impl std::hash::Hash for DocumentColor {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// The link as reported by the language server, its target is absent until resolved.
+    pub lsp_link: lsp::DocumentLink,
+    /// The language server that reported the link, and the one to resolve it with.
+    pub server_id: LanguageServerId,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ColorPresentation {
     pub label: SharedString,
@@ -3823,6 +3836,78 @@ impl Project {
         })
     }
 
+    pub fn incoming_calls<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.incoming_calls(buffer, position, cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn outgoing_calls<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.outgoing_calls(buffer, position, cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn supertypes<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.supertypes(buffer, position, cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn subtypes<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Location>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self
+            .lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.subtypes(buffer, position, cx));
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
     pub fn document_highlights<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -3919,6 +4004,46 @@ impl Project {
             .update(cx, |lsp_store, cx| lsp_store.hover(buffer, position, cx))
     }
 
+    pub fn folding_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<Range<Anchor>>>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.folding_ranges(buffer, cx))
+    }
+
+    pub fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx))
+    }
+
+    pub fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(buffer, link, cx)
+        })
+    }
+
+    pub fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<Vec<Vec<Range<Anchor>>>>>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.selection_ranges(buffer, positions, cx)
+        })
+    }
+
     pub fn linked_edits(
         &self,
         buffer: &Entity<Buffer>,
@@ -3957,6 +4082,17 @@ impl Project {
         })
     }
 
+    pub fn resolve_code_lens(
+        &self,
+        buffer: &Entity<Buffer>,
+        action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_code_lens(buffer, action, cx)
+        })
+    }
+
     pub fn code_lens_actions<T: Clone + ToOffset>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -4086,22 +4222,57 @@ impl Project {
             .summary_for_anchor::<text::PointUtf16>(&range.end)
             .row as usize;
 
-        let inline_value_locations = provide_inline_values(captures, &snapshot, row);
+        let tree_sitter_locations = provide_inline_values(captures, &snapshot, row);
+
+        let lsp_range_start = snapshot
+            .summary_for_anchor::<Point>(&range.start)
+            .max(Point::new(
+                (row as u32).saturating_sub(INLINE_VALUE_ROWS_ABOVE_STOPPED_LINE),
+                0,
+            ));
+        let lsp_range = snapshot.anchor_before(lsp_range_start)..range.end;
 
         let stack_frame_id = active_stack_frame.stack_frame_id;
+        // The values are only valid for the frame that the debuggee stopped in, so there is
+        // nothing to reuse across steps, and the request is cheap next to the debug adapter
+        // lookups that follow it.
+        let lsp_inline_values = match i32::try_from(stack_frame_id) {
+            Ok(frame_id) => self.request_lsp(
+                buffer_handle.clone(),
+                LanguageServerToQuery::FirstCapable,
+                GetInlineValues {
+                    range: lsp_range,
+                    stopped_location: snapshot.anchor_before(Point::new(row as u32, 0))..range.end,
+                    frame_id,
+                },
+                cx,
+            ),
+            Err(_) => Task::ready(Ok(Vec::new())),
+        };
         cx.spawn(async move |this, cx| {
-            this.update(cx, |project, cx| {
-                project.dap_store().update(cx, |dap_store, cx| {
-                    dap_store.resolve_inline_value_locations(
-                        session,
-                        stack_frame_id,
-                        buffer_handle,
-                        inline_value_locations,
-                        cx,
-                    )
-                })
-            })?
-            .await
+            let lsp_inline_values = lsp_inline_values.await.log_err().unwrap_or_default();
+            // Language servers know the language semantics better, so only fall back to the
+            // tree-sitter debugger queries when none of them provides inline values.
+            let (inline_value_locations, text_inline_values) = if lsp_inline_values.is_empty() {
+                (tree_sitter_locations, Vec::new())
+            } else {
+                inline_values_from_lsp(lsp_inline_values, &snapshot)
+            };
+            let mut inlay_hints = this
+                .update(cx, |project, cx| {
+                    project.dap_store().update(cx, |dap_store, cx| {
+                        dap_store.resolve_inline_value_locations(
+                            session,
+                            stack_frame_id,
+                            buffer_handle,
+                            inline_value_locations,
+                            cx,
+                        )
+                    })
+                })?
+                .await?;
+            inlay_hints.extend(text_inline_values);
+            Ok(inlay_hints)
         })
     }
 
@@ -5815,6 +5986,58 @@ fn provide_inline_values(
     variables
 }
 
+/// Splits the language server's inline values into the ones that still need to be looked up in the
+/// debug session, and the ones that are already rendered as text.
+fn inline_values_from_lsp(
+    inline_values: Vec<lsp::InlineValue>,
+    snapshot: &language::BufferSnapshot,
+) -> (Vec<InlineValueLocation>, Vec<InlayHint>) {
+    let mut locations = Vec::new();
+    let mut text_hints = Vec::new();
+    for inline_value in inline_values {
+        let (range, variable_name, lookup) = match inline_value {
+            lsp::InlineValue::Text(text) => {
+                let position =
+                    snapshot.clip_point_utf16(language::point_from_lsp(text.range.end), Bias::Left);
+                // Debugger inlays ignore the padding, so separate the text from the code here.
+                text_hints.push(InlayHint {
+                    position: snapshot.anchor_after(position),
+                    label: InlayHintLabel::String(format!(" {}", text.text)),
+                    kind: Some(InlayHintKind::Type),
+                    padding_left: false,
+                    padding_right: false,
+                    tooltip: None,
+                    resolve_state: ResolveState::Resolved,
+                });
+                continue;
+            }
+            lsp::InlineValue::VariableLookup(lookup) => (
+                lookup.range,
+                lookup.variable_name,
+                VariableLookupKind::Variable,
+            ),
+            lsp::InlineValue::EvaluatableExpression(expression) => (
+                expression.range,
+                expression.expression,
+                VariableLookupKind::Expression,
+            ),
+        };
+        let start = snapshot.clip_point_utf16(language::point_from_lsp(range.start), Bias::Left);
+        let end = snapshot.clip_point_utf16(language::point_from_lsp(range.end), Bias::Left);
+        let variable_name = variable_name
+            .unwrap_or_else(|| snapshot.text_for_range(start..end).collect::<String>());
+        let end = snapshot.point_utf16_to_point(end);
+        locations.push(InlineValueLocation {
+            variable_name,
+            scope: VariableScope::Local,
+            lookup,
+            row: end.row as usize,
+            column: end.column as usize,
+        });
+    }
+    (locations, text_hints)
+}
+
 #[cfg(test)]
 mod disable_ai_settings_tests {
     use super::*;
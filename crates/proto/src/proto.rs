@@ -312,6 +312,22 @@ messages!(
     (GitRenameBranch, Background),
     (TrustWorktrees, Background),
     (RestrictWorktrees, Background),
+    (PrepareHierarchy, Background),
+    (PrepareHierarchyResponse, Background),
+    (GetHierarchyLocations, Background),
+    (GetHierarchyLocationsResponse, Background),
+    (GetInlineValues, Background),
+    (GetInlineValuesResponse, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
+    (ResolveCodeLens, Background),
+    (ResolveCodeLensResponse, Background),
     (CheckForPushedCommits, Background),
     (CheckForPushedCommitsResponse, Background),
     (GitDiff, Background),
@@ -534,6 +550,14 @@ request_messages!(
     (GitCreateWorktree, Ack),
     (TrustWorktrees, Ack),
     (RestrictWorktrees, Ack),
+    (PrepareHierarchy, PrepareHierarchyResponse),
+    (GetHierarchyLocations, GetHierarchyLocationsResponse),
+    (GetInlineValues, GetInlineValuesResponse),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
+    (ResolveCodeLens, ResolveCodeLensResponse),
 );
 
 lsp_messages!(
@@ -709,6 +733,14 @@ entity_messages!(
     GitCreateWorktree,
     TrustWorktrees,
     RestrictWorktrees,
+    PrepareHierarchy,
+    GetHierarchyLocations,
+    GetInlineValues,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
+    ResolveCodeLens,
 );
 
 entity_messages!(
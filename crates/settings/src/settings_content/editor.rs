@@ -202,6 +202,10 @@ pub struct EditorSettingsContent {
     ///
     /// Default: [`DocumentColorsRenderMode::Inlay`]
     pub lsp_document_colors: Option<DocumentColorsRenderMode>,
+    /// Whether to show LSP `textDocument/codeLens` annotations above the lines they refer to.
+    ///
+    /// Default: false
+    pub lsp_code_lens: Option<bool>,
     /// When to show the scrollbar in the completion menu.
     /// This setting can take four values:
     ///
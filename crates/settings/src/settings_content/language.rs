@@ -167,6 +167,11 @@ pub struct EditPredictionSettingsContent {
     /// Whether edit predictions are enabled in the assistant prompt editor.
     /// This has no effect if globally disabled.
     pub enabled_in_text_threads: Option<bool>,
+    /// Whether predicted text shown inline in the buffer is syntax highlighted,
+    /// at reduced opacity, instead of using a single color.
+    ///
+    /// Default: false
+    pub syntax_highlighting: Option<bool>,
 }
 
 #[with_fallible_options]
@@ -437,6 +442,15 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: false
     pub colorize_brackets: Option<bool>,
+    /// Whether to use tree-sitter locals queries to mark local variables that shadow
+    /// another variable of the same name.
+    ///
+    /// Default: false
+    pub highlight_shadowed_variables: Option<bool>,
+    /// Which source of folding ranges to prefer for this language.
+    ///
+    /// Default: "indentation"
+    pub folding_provider: Option<FoldingProvider>,
 }
 
 /// Controls how whitespace should be displayedin the editor.
@@ -504,6 +518,30 @@ pub enum RewrapBehavior {
     Anywhere,
 }
 
+/// Controls where folding ranges come from.
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FoldingProvider {
+    /// Fold by indentation only.
+    #[default]
+    Indentation,
+    /// Prefer the folding ranges provided by the language server (regions, imports, comments),
+    /// falling back to indentation for lines the server provides no range for.
+    LanguageServer,
+}
+
 #[with_fallible_options]
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
 pub struct JsxTagAutoCloseSettingsContent {
@@ -66,6 +66,9 @@ pub struct ThemeSettingsContent {
     #[schemars(range(min = 0.0, max = 0.9))]
     pub unnecessary_code_fade: Option<CodeFade>,
 
+    /// Settings for coloring syntax elements with the theme's accent colors.
+    pub rainbow: Option<RainbowSettingsContent>,
+
     /// EXPERIMENTAL: Overrides for the current theme.
     ///
     /// These values will override the ones on the current theme specified in `theme`.
@@ -79,6 +82,18 @@ pub struct ThemeSettingsContent {
     pub theme_overrides: HashMap<String, ThemeStyleContent>,
 }
 
+/// Settings for coloring syntax elements with the theme's accent colors.
+#[with_fallible_options]
+#[derive(
+    Default, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+pub struct RainbowSettingsContent {
+    /// Whether to color each Markdown heading level with its own accent color.
+    ///
+    /// Default: false
+    pub headings: Option<bool>,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -721,6 +736,12 @@ pub struct ThemeColorsContent {
     #[serde(rename = "editor.document_highlight.bracket_background")]
     pub editor_document_highlight_bracket_background: Option<String>,
 
+    /// Linked editing ranges background color.
+    ///
+    /// Ranges that are edited together with the one under the cursor, like a matching HTML tag, are highlighted with this background color.
+    #[serde(rename = "editor.linked_edit_background")]
+    pub editor_linked_edit_background: Option<String>,
+
     /// Terminal background color.
     #[serde(rename = "terminal.background")]
     pub terminal_background: Option<String>,
@@ -265,6 +265,7 @@ impl VsCodeSettings {
             hover_popover_enabled: self.read_bool("editor.hover.enabled"),
             inline_code_actions: None,
             jupyter: None,
+            lsp_code_lens: self.read_bool("editor.codeLens"),
             lsp_document_colors: None,
             lsp_highlight_debounce: None,
             middle_click_paste: None,
@@ -430,6 +431,11 @@ impl VsCodeSettings {
             enable_language_server: None,
             ensure_final_newline_on_save: self.read_bool("files.insertFinalNewline"),
             extend_comment_on_newline: None,
+            folding_provider: self.read_enum("editor.foldingStrategy", |s| match s {
+                "auto" => Some(FoldingProvider::LanguageServer),
+                "indentation" => Some(FoldingProvider::Indentation),
+                _ => None,
+            }),
             format_on_save: self.read_bool("editor.guides.formatOnSave").map(|b| {
                 if b {
                     FormatOnSave::On
@@ -439,6 +445,7 @@ impl VsCodeSettings {
             }),
             formatter: None,
             hard_tabs: self.read_bool("editor.insertSpaces").map(|v| !v),
+            highlight_shadowed_variables: None,
             indent_guides: skip_default(IndentGuideSettingsContent {
                 enabled: self.read_bool("editor.guides.indentation"),
                 ..Default::default()
@@ -803,6 +810,7 @@ impl VsCodeSettings {
             icon_theme: None,
             ui_density: None,
             unnecessary_code_fade: None,
+            rainbow: None,
             experimental_theme_overrides: None,
             theme_overrides: Default::default(),
         }
@@ -959,6 +959,29 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Rainbow Headings",
+                    description: "Whether to color each Markdown heading level with its own accent color.",
+                    field: Box::new(SettingField {
+                        json_path: Some("rainbow.headings"),
+                        pick: |settings_content| {
+                            settings_content
+                                .theme
+                                .rainbow
+                                .as_ref()
+                                .and_then(|rainbow| rainbow.headings.as_ref())
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .theme
+                                .rainbow
+                                .get_or_insert_default()
+                                .headings = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Current Line Highlight",
                     description: "How to highlight the current line.",
@@ -6133,6 +6156,21 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                             metadata: None,
                             files: USER,
                         }),
+                        SettingsPageItem::SettingItem(SettingItem {
+                            title: "Syntax Highlighting",
+                            description: "Whether predicted text shown inline in the buffer is syntax highlighted, at reduced opacity, instead of using a single color.",
+                            field: Box::new(SettingField {
+                                json_path: Some("edit_prediction.syntax_highlighting"),
+                                pick: |settings_content| {
+                                    settings_content.project.all_languages.edit_predictions.as_ref()?.syntax_highlighting.as_ref()
+                                },
+                                write: |settings_content, value| {
+                                    settings_content.project.all_languages.edit_predictions.get_or_insert_default().syntax_highlighting = value;
+                                },
+                            }),
+                            metadata: None,
+                            files: USER,
+                        }),
                     ]
                 );
                 items
@@ -7077,19 +7115,34 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
         }),
     ];
     if current_language().is_none() {
-        items.push(SettingsPageItem::SettingItem(SettingItem {
-            title: "LSP Document Colors",
-            description: "How to render LSP color previews in the editor.",
-            field: Box::new(SettingField {
-                json_path: Some("lsp_document_colors"),
-                pick: |settings_content| settings_content.editor.lsp_document_colors.as_ref(),
-                write: |settings_content, value| {
-                    settings_content.editor.lsp_document_colors = value;
-                },
+        items.extend([
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "LSP Document Colors",
+                description: "How to render LSP color previews in the editor.",
+                field: Box::new(SettingField {
+                    json_path: Some("lsp_document_colors"),
+                    pick: |settings_content| settings_content.editor.lsp_document_colors.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.editor.lsp_document_colors = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
             }),
-            metadata: None,
-            files: USER,
-        }))
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "LSP Code Lens",
+                description: "Whether to show LSP code lens annotations above the lines they refer to.",
+                field: Box::new(SettingField {
+                    json_path: Some("lsp_code_lens"),
+                    pick: |settings_content| settings_content.editor.lsp_code_lens.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.editor.lsp_code_lens = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ])
     }
     items.extend([
         SettingsPageItem::SectionHeader("Tasks"),
@@ -7247,6 +7300,44 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | PROJECT,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Highlight Shadowed Variables",
+            description: "Whether to mark local variables that shadow another variable of the same name.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).highlight_shadowed_variables"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.highlight_shadowed_variables.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.highlight_shadowed_variables = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Folding Provider",
+            description: "Which source of folding ranges to prefer.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).folding_provider"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.folding_provider.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.folding_provider = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
     ]);
 
     if current_language().is_none() {
@@ -181,6 +181,7 @@ pub(crate) fn zed_default_dark() -> Theme {
                 ),
                 editor_document_highlight_write_background: gpui::red(),
                 editor_document_highlight_bracket_background: gpui::green(),
+                editor_linked_edit_background: gpui::blue(),
 
                 terminal_background: bg,
                 // todo("Use one colors for terminal")
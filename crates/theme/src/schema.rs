@@ -595,6 +595,10 @@ pub fn theme_colors_refinement(
             .and_then(|color| try_parse_color(color).ok())
             // Fall back to `editor.document_highlight.read_background`, for backwards compatibility.
             .or(editor_document_highlight_read_background),
+        editor_linked_edit_background: this
+            .editor_linked_edit_background
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok()),
         terminal_background: this
             .terminal_background
             .as_ref()
@@ -136,6 +136,8 @@ pub struct ThemeSettings {
     pub ui_density: UiDensity,
     /// The amount of fading applied to unnecessary code.
     pub unnecessary_code_fade: f32,
+    /// Whether each Markdown heading level is colored with its own accent color.
+    pub rainbow_headings: bool,
 }
 
 pub(crate) const DEFAULT_LIGHT_THEME: &'static str = "One Light";
@@ -555,6 +557,19 @@ impl ThemeSettings {
             arc_theme = Arc::new(theme);
         }
 
+        // Applied last so the headings pick up any overridden accent colors.
+        if self.rainbow_headings {
+            let mut theme = (*arc_theme).clone();
+            theme.styles.syntax = SyntaxTheme::merge(
+                theme.styles.syntax.clone(),
+                theme
+                    .styles
+                    .syntax
+                    .rainbow_heading_styles(&theme.styles.accents),
+            );
+            arc_theme = Arc::new(theme);
+        }
+
         arc_theme
     }
 
@@ -744,6 +759,7 @@ impl settings::Settings for ThemeSettings {
             icon_theme: icon_theme_selection,
             ui_density: content.ui_density.unwrap_or_default().into(),
             unnecessary_code_fade: content.unnecessary_code_fade.unwrap().0.clamp(0.0, 0.9),
+            rainbow_headings: content.rainbow.unwrap().headings.unwrap(),
         }
     }
 }
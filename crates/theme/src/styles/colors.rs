@@ -223,6 +223,10 @@ pub struct ThemeColors {
     ///
     /// Matching brackets in the cursor scope are highlighted with this background color.
     pub editor_document_highlight_bracket_background: Hsla,
+    /// Linked editing ranges background color.
+    ///
+    /// Ranges that are edited together with the one under the cursor, like a matching HTML tag, are highlighted with this background color.
+    pub editor_linked_edit_background: Hsla,
 
     // ===
     // Terminal
@@ -389,6 +393,7 @@ pub enum ThemeColorField {
     EditorDocumentHighlightReadBackground,
     EditorDocumentHighlightWriteBackground,
     EditorDocumentHighlightBracketBackground,
+    EditorLinkedEditBackground,
     TerminalBackground,
     TerminalForeground,
     TerminalBrightForeground,
@@ -515,6 +520,7 @@ impl ThemeColors {
             ThemeColorField::EditorDocumentHighlightBracketBackground => {
                 self.editor_document_highlight_bracket_background
             }
+            ThemeColorField::EditorLinkedEditBackground => self.editor_linked_edit_background,
             ThemeColorField::TerminalBackground => self.terminal_background,
             ThemeColorField::TerminalForeground => self.terminal_foreground,
             ThemeColorField::TerminalBrightForeground => self.terminal_bright_foreground,
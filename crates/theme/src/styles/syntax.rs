@@ -4,6 +4,8 @@ use std::sync::Arc;
 
 use gpui::{HighlightStyle, Hsla};
 
+use crate::AccentColors;
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SyntaxTheme {
     pub highlights: Vec<(String, HighlightStyle)>,
@@ -51,6 +53,31 @@ impl SyntaxTheme {
         Some(ix as u32)
     }
 
+    /// Returns a style for each Markdown heading level (`title.markup.h1` through
+    /// `title.markup.h6`), keeping the theme's title style but coloring each level
+    /// with its own accent.
+    pub fn rainbow_heading_styles(&self, accents: &AccentColors) -> Vec<(String, HighlightStyle)> {
+        let title_style = self
+            .highlights
+            .iter()
+            .find(|(name, _)| name == "title.markup")
+            .or_else(|| self.highlights.iter().find(|(name, _)| name == "title"))
+            .map(|(_, style)| *style)
+            .unwrap_or_default();
+
+        (1..=6)
+            .map(|level| {
+                (
+                    format!("title.markup.h{level}"),
+                    HighlightStyle {
+                        color: Some(accents.color_for_index(level - 1)),
+                        ..title_style
+                    },
+                )
+            })
+            .collect()
+    }
+
     /// Returns a new [`Arc<SyntaxTheme>`] with the given syntax styles merged in.
     pub fn merge(base: Arc<Self>, user_syntax_styles: Vec<(String, HighlightStyle)>) -> Arc<Self> {
         if user_syntax_styles.is_empty() {
@@ -89,7 +116,7 @@ impl SyntaxTheme {
 
 #[cfg(test)]
 mod tests {
-    use gpui::FontStyle;
+    use gpui::{FontStyle, FontWeight};
 
     use super::*;
 
@@ -194,4 +221,37 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn test_rainbow_heading_styles() {
+        let title_style = HighlightStyle {
+            color: Some(gpui::red()),
+            font_weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        };
+        let syntax_theme = Arc::new(SyntaxTheme::new_test_styles([("title", title_style)]));
+        let accents = AccentColors(vec![gpui::green(), gpui::blue()]);
+
+        let syntax_theme = SyntaxTheme::merge(
+            syntax_theme.clone(),
+            syntax_theme.rainbow_heading_styles(&accents),
+        );
+
+        // Levels cycle through the accents and keep the rest of the title style.
+        for (level, color) in [
+            (1, gpui::green()),
+            (2, gpui::blue()),
+            (3, gpui::green()),
+            (6, gpui::blue()),
+        ] {
+            assert_eq!(
+                syntax_theme.get(&format!("title.markup.h{level}")),
+                HighlightStyle {
+                    color: Some(color),
+                    ..title_style
+                }
+            );
+        }
+        assert_eq!(syntax_theme.get("title"), title_style);
+    }
 }
@@ -134,6 +134,7 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
     let mut prev_theme_overrides = (
         settings.experimental_theme_overrides.clone(),
         settings.theme_overrides.clone(),
+        settings.rainbow_headings,
     );
 
     cx.observe_global::<SettingsStore>(move |cx| {
@@ -148,6 +149,7 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
         let theme_overrides = (
             settings.experimental_theme_overrides.clone(),
             settings.theme_overrides.clone(),
+            settings.rainbow_headings,
         );
 
         if buffer_font_size_settings != prev_buffer_font_size_settings {
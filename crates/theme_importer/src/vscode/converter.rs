@@ -7,7 +7,7 @@ use theme::{
 };
 
 use crate::ThemeMetadata;
-use crate::vscode::{VsCodeTheme, VsCodeTokenScope};
+use crate::vscode::{VsCodeSemanticTokenColor, VsCodeTheme, VsCodeTokenScope};
 
 use super::ZedSyntaxToken;
 
@@ -26,6 +26,36 @@ pub(crate) fn try_parse_font_style(font_style: &str) -> Option<FontStyleContent>
     }
 }
 
+fn apply_semantic_token_color(
+    highlight_style: &mut HighlightStyleContent,
+    semantic_token_color: &VsCodeSemanticTokenColor,
+) {
+    match semantic_token_color {
+        VsCodeSemanticTokenColor::Foreground(foreground) => {
+            highlight_style.color = Some(foreground.clone());
+        }
+        VsCodeSemanticTokenColor::Style(style) => {
+            if let Some(foreground) = &style.foreground {
+                highlight_style.color = Some(foreground.clone());
+            }
+            if let Some(font_style) = &style.font_style {
+                highlight_style.font_style = try_parse_font_style(font_style);
+                highlight_style.font_weight = try_parse_font_weight(font_style);
+            }
+            match style.italic {
+                Some(true) => highlight_style.font_style = Some(FontStyleContent::Italic),
+                Some(false) => highlight_style.font_style = None,
+                None => {}
+            }
+            match style.bold {
+                Some(true) => highlight_style.font_weight = Some(FontWeightContent::Bold),
+                Some(false) => highlight_style.font_weight = None,
+                None => {}
+            }
+        }
+    }
+}
+
 pub struct VsCodeThemeConverter {
     theme: VsCodeTheme,
     theme_metadata: ThemeMetadata,
@@ -232,38 +262,55 @@ impl VsCodeThemeConverter {
                     })
                 });
 
-            let Some(token_color) = best_match else {
-                log::warn!("No matching token color found for '{syntax_token}'");
-                continue;
+            let semantic_match = if self.theme.semantic_highlighting == Some(true) {
+                syntax_token.find_best_semantic_token_color(&self.theme.semantic_token_colors)
+            } else {
+                None
             };
 
-            log::info!(
-                "Matched '{syntax_token}' to '{}'",
-                token_color
-                    .name
-                    .clone()
-                    .or_else(|| token_color
-                        .scope
-                        .as_ref()
-                        .map(|scope| format!("{:?}", scope)))
-                    .unwrap_or_else(|| "no identifier".to_string())
-            );
+            let mut highlight_style = match best_match {
+                Some(token_color) => {
+                    log::info!(
+                        "Matched '{syntax_token}' to '{}'",
+                        token_color
+                            .name
+                            .clone()
+                            .or_else(|| token_color
+                                .scope
+                                .as_ref()
+                                .map(|scope| format!("{:?}", scope)))
+                            .unwrap_or_else(|| "no identifier".to_string())
+                    );
 
-            let highlight_style = HighlightStyleContent {
-                color: token_color.settings.foreground.clone(),
-                background_color: token_color.settings.background.clone(),
-                font_style: token_color
-                    .settings
-                    .font_style
-                    .as_ref()
-                    .and_then(|style| try_parse_font_style(style)),
-                font_weight: token_color
-                    .settings
-                    .font_style
-                    .as_ref()
-                    .and_then(|style| try_parse_font_weight(style)),
+                    HighlightStyleContent {
+                        color: token_color.settings.foreground.clone(),
+                        background_color: token_color.settings.background.clone(),
+                        font_style: token_color
+                            .settings
+                            .font_style
+                            .as_ref()
+                            .and_then(|style| try_parse_font_style(style)),
+                        font_weight: token_color
+                            .settings
+                            .font_style
+                            .as_ref()
+                            .and_then(|style| try_parse_font_weight(style)),
+                    }
+                }
+                None if semantic_match.is_some() => HighlightStyleContent::default(),
+                None => {
+                    log::warn!("No matching token color found for '{syntax_token}'");
+                    continue;
+                }
             };
 
+            // VS Code gives semantic token rules precedence over TextMate scopes, while still
+            // inheriting whatever the semantic rule leaves unset.
+            if let Some(semantic_token_color) = semantic_match {
+                log::info!("Matched '{syntax_token}' to a semantic token color");
+                apply_semantic_token_color(&mut highlight_style, semantic_token_color);
+            }
+
             if highlight_style.is_empty() {
                 continue;
             }
@@ -24,6 +24,23 @@ pub struct VsCodeTokenColorSettings {
     pub font_style: Option<String>,
 }
 
+/// A `semanticTokenColors` rule, which is either a bare foreground color or a style object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum VsCodeSemanticTokenColor {
+    Foreground(String),
+    Style(VsCodeSemanticTokenStyle),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VsCodeSemanticTokenStyle {
+    pub foreground: Option<String>,
+    #[serde(rename = "fontStyle")]
+    pub font_style: Option<String>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+}
+
 #[derive(Debug, PartialEq, Copy, Clone, EnumIter)]
 pub enum ZedSyntaxToken {
     Attribute,
@@ -173,6 +190,25 @@ impl ZedSyntaxToken {
         Some(matches)
     }
 
+    /// Returns the semantic token color of the first selector (in priority order) that the theme
+    /// declares.
+    ///
+    /// Language-specific selectors (`function:rust`) are skipped, as the imported theme
+    /// applies to every language.
+    pub fn find_best_semantic_token_color<'a>(
+        &self,
+        semantic_token_colors: &'a IndexMap<String, VsCodeSemanticTokenColor>,
+    ) -> Option<&'a VsCodeSemanticTokenColor> {
+        self.to_vscode_semantic_selectors()
+            .into_iter()
+            .find_map(|selector| {
+                semantic_token_colors
+                    .iter()
+                    .find(|(candidate, _)| candidate.trim() == selector)
+                    .map(|(_, semantic_token_color)| semantic_token_color)
+            })
+    }
+
     pub fn fallbacks(&self) -> &[Self] {
         match self {
             ZedSyntaxToken::CommentDoc => &[ZedSyntaxToken::Comment],
@@ -190,6 +226,29 @@ impl ZedSyntaxToken {
         }
     }
 
+    fn to_vscode_semantic_selectors(self) -> Vec<&'static str> {
+        match self {
+            ZedSyntaxToken::Attribute => vec!["decorator"],
+            ZedSyntaxToken::Comment => vec!["comment"],
+            ZedSyntaxToken::CommentDoc => vec!["comment.documentation"],
+            ZedSyntaxToken::Constant => vec!["variable.readonly", "*.readonly"],
+            ZedSyntaxToken::Enum => vec!["enum"],
+            ZedSyntaxToken::Function => vec!["function", "method"],
+            ZedSyntaxToken::Keyword => vec!["keyword"],
+            ZedSyntaxToken::Label => vec!["label"],
+            ZedSyntaxToken::Number => vec!["number"],
+            ZedSyntaxToken::Operator => vec!["operator"],
+            ZedSyntaxToken::Preproc => vec!["macro"],
+            ZedSyntaxToken::Property => vec!["property"],
+            ZedSyntaxToken::String => vec!["string"],
+            ZedSyntaxToken::StringRegex => vec!["regexp"],
+            ZedSyntaxToken::Type => vec!["type", "class", "struct", "interface", "typeParameter"],
+            ZedSyntaxToken::Variable => vec!["variable", "parameter"],
+            ZedSyntaxToken::Variant => vec!["enumMember"],
+            _ => vec![],
+        }
+    }
+
     fn to_vscode(self) -> Vec<&'static str> {
         match self {
             ZedSyntaxToken::Attribute => vec!["entity.other.attribute-name"],
@@ -308,3 +367,42 @@ impl ZedSyntaxToken {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_best_semantic_token_color() {
+        let semantic_token_colors: IndexMap<String, VsCodeSemanticTokenColor> =
+            serde_json::from_str(
+                r##"{
+                    "function:rust": "#111111",
+                    "method": { "foreground": "#222222", "italic": true },
+                    "class": "#333333",
+                    "type": "#444444"
+                }"##,
+            )
+            .unwrap();
+
+        let Some(VsCodeSemanticTokenColor::Style(style)) =
+            ZedSyntaxToken::Function.find_best_semantic_token_color(&semantic_token_colors)
+        else {
+            panic!("expected `method` to match `function`");
+        };
+        assert_eq!(style.foreground.as_deref(), Some("#222222"));
+
+        let Some(VsCodeSemanticTokenColor::Foreground(color)) =
+            ZedSyntaxToken::Type.find_best_semantic_token_color(&semantic_token_colors)
+        else {
+            panic!("expected `type` to match `type`");
+        };
+        assert_eq!(color, "#444444");
+
+        assert!(
+            ZedSyntaxToken::Variable
+                .find_best_semantic_token_color(&semantic_token_colors)
+                .is_none()
+        );
+    }
+}
@@ -1,7 +1,8 @@
+use indexmap::IndexMap;
 use serde::Deserialize;
 use vscode_theme::Colors;
 
-use crate::vscode::VsCodeTokenColor;
+use crate::vscode::{VsCodeSemanticTokenColor, VsCodeTokenColor};
 
 #[derive(Deserialize, Debug)]
 pub struct VsCodeTheme {
@@ -28,12 +29,11 @@ pub struct VsCodeTheme {
         reason = "This field was found to be unused with serde library bump; it's left as is due to insufficient context on PO's side, but it *may* be fine to remove"
     )]
     pub semantic_class: Option<String>,
-    #[expect(
-        unused,
-        reason = "This field was found to be unused with serde library bump; it's left as is due to insufficient context on PO's side, but it *may* be fine to remove"
-    )]
     #[serde(rename = "semanticHighlighting")]
     pub semantic_highlighting: Option<bool>,
+    /// Only applied by VS Code when `semanticHighlighting` is enabled.
+    #[serde(rename = "semanticTokenColors", default)]
+    pub semantic_token_colors: IndexMap<String, VsCodeSemanticTokenColor>,
     pub colors: Colors,
     #[serde(rename = "tokenColors")]
     pub token_colors: Vec<VsCodeTokenColor>,